@@ -0,0 +1,85 @@
+use std::sync::atomic::AtomicBool;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use crate::global;
+use crate::parser;
+use crate::hash_counter::HashCounter;
+use crate::logger::Logger;
+use crate::reporter::UciReporter;
+use crate::searcher::Searcher;
+use crate::searchtype::SearchType;
+
+pub const DEFAULT_BENCH_DEPTH: u64 = 5;
+
+//fixed set of positions, changing this list changes the signature
+pub const BENCH_FENS: [&str; 11] = [
+    global::FEN_STARTPOS,
+    "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3",
+    "r1bq1rk1/pp2bppp/2n1pn2/2pp4/3P4/2PBPN2/PP1N1PPP/R1BQ1RK1 w - - 0 8",
+    "4rrk1/pp1n3p/3q2pQ/2p1pb2/2PP4/2P3N1/P2B2PP/4RRK1 b - - 7 19",
+    "r3r1k1/2p2ppp/p1p1bn2/8/1q2P3/2NPQN2/PPP3PP/R4RK1 b - - 2 15",
+    "r1bbk1nr/pp3p1p/2n5/1N4p1/2Np1B2/8/PPP2PPP/2KR1B1R w kq - 0 13",
+    "3r1rk1/p5pp/bpp1pp2/8/q1PP1P2/b3P3/P2NQRPP/1R2B1K1 b - - 6 22",
+    "8/k1p5/1p1p4/p2P1p2/P1P2P2/1P2K3/8/8 w - - 4 45",
+    "8/8/4kpp1/3p1b2/p6P/2B5/6P1/6K1 b - - 2 47",
+    "6k1/6p1/6Pp/ppp5/3pn2P/1P3K2/1PP2P2/3N4 b - - 0 1",
+    "8/8/8/3k4/8/8/3PK3/8 w - - 0 1"
+];
+
+pub struct BenchResult {
+    pub nodes: u64,
+    pub time: u64,
+    pub nps: u64
+}
+
+impl BenchResult {
+    //total node count is deterministic for a given depth, so it doubles as a signature
+    pub fn get_signature(&self) -> u64 {
+        self.nodes
+    }
+}
+
+pub fn run(depth: u64, logger: &Logger) -> BenchResult {
+    let start_time = SystemTime::now();
+    let mut nodes: u64 = 0;
+
+    for (i, fen) in BENCH_FENS.iter().enumerate() {
+        let fen_parts = fen.split(' ').collect::<Vec<&str>>();
+        let position = parser::parse_fen(&fen_parts).expect("Error in bench FEN");
+
+        logger.send(&format!("Position: {}/{} ({})", i + 1, BENCH_FENS.len(), fen));
+
+        //the receiver is never read, the search is run synchronously
+        let (_sender, receiver) = mpsc::channel();
+        let mut searcher = Searcher::new(receiver, position, Arc::new(AtomicBool::new(false)), HashCounter::new());
+        searcher.set_reporter(Box::new(UciReporter::new(logger.clone())));
+        searcher.find_best_move(SearchType::Depth(depth)).expect("Bench position has no legal move");
+        nodes += searcher.get_node_count() as u64;
+    }
+
+    let dur = SystemTime::now()
+        .duration_since(start_time)
+        .expect("SystemTime::duration_since failed");
+    let time = 1000 * dur.as_secs() + dur.subsec_millis() as u64;
+
+    let nps = (nodes * 1000).checked_div(time).unwrap_or(nodes);
+
+    BenchResult { nodes, time, nps }
+}
+
+pub fn get_depth(args: &str) -> Option<u64> {
+    match args.split_whitespace().next() {
+        Some(s) => s.parse::<u64>().ok(),
+        None => Some(DEFAULT_BENCH_DEPTH)
+    }
+}
+
+pub fn print_result(result: &BenchResult, logger: &Logger) {
+    logger.send("===========================");
+    logger.send(&format!("Total time (ms) : {}", result.time));
+    logger.send(&format!("Nodes searched  : {}", result.nodes));
+    logger.send(&format!("Nodes/second    : {}", result.nps));
+    logger.send(&format!("Signature       : {}", result.get_signature()));
+}
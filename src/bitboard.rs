@@ -13,8 +13,9 @@ const H_FILE: u64 = 0x8080808080808080;
 const LS_BIT: u64 = 0x0000000000000001;
 const MS_BIT: u64 = 0x8000000000000000;
 const PAWN_START_RANKS: u64 = 0x00FF00000000FF00;
+const RANK_1: u64 = 0x00000000000000FF;
 
-#[derive(Clone, Copy, Eq, Hash)]
+#[derive(Clone, Copy, Debug, Eq, Hash)]
 pub struct BitBoard(u64);
 
 impl BitBoard {
@@ -22,6 +23,23 @@ impl BitBoard {
         BitBoard(0)
     }
 
+    pub fn from_u64(value: u64) -> BitBoard {
+        BitBoard(value)
+    }
+
+    pub fn to_u64(self) -> u64 {
+        self.0
+    }
+
+    //x and y as in Square::from_xy
+    pub fn from_file(x: u8) -> BitBoard {
+        BitBoard(A_FILE << x)
+    }
+
+    pub fn from_rank(y: u8) -> BitBoard {
+        BitBoard(RANK_1 << (8 * y))
+    }
+
     pub fn pawn_start_ranks_board() -> BitBoard {
         BitBoard(PAWN_START_RANKS)
     }
@@ -1,9 +1,23 @@
+use crate::logger::Logger;
+
+pub const OPTION_DEBUG_LOG_FILE: &str = "Debug Log File";
+pub const OPTION_CONTEMPT: &str = "Contempt";
+pub const OPTION_ANALYSIS_CONTEMPT: &str = "Analysis Contempt";
+pub const OPTION_ANALYSE_MODE: &str = "UCI_AnalyseMode";
+pub const OPTION_EVAL_FILE: &str = "EvalFile";
+pub const OPTION_USE_NNUE: &str = "Use NNUE";
+pub const OPTION_NNUE_FILE: &str = "NNUE File";
+
+pub const DEFAULT_CONTEMPT: i32 = 0;
+pub const MIN_CONTEMPT: i32 = -100;
+pub const MAX_CONTEMPT: i32 = 100;
+
 #[allow(dead_code)]
 pub enum InputCommand {
     Uci,
-    Debug,
+    Debug(bool),
     IsReady,
-    SetOption,
+    SetOption(String),
     Register,
     UciNewGame,
     Position(String),
@@ -11,6 +25,29 @@ pub enum InputCommand {
     Stop,
     PonderHit,
     Quit,
+    Bench(String),
+    Eval,
+}
+
+impl InputCommand {
+    //the command as sent over the wire, used when haddock is the GUI side (uci client)
+    pub fn to_uci(&self) -> String {
+        match self {
+            InputCommand::Uci => "uci".to_string(),
+            InputCommand::Debug(on) => format!("debug {}", if *on { "on" } else { "off" }),
+            InputCommand::IsReady => "isready".to_string(),
+            InputCommand::SetOption(args) => format!("setoption {}", args),
+            InputCommand::Register => "register".to_string(),
+            InputCommand::UciNewGame => "ucinewgame".to_string(),
+            InputCommand::Position(args) => format!("position {}", args),
+            InputCommand::Go(args) => format!("go {}", args),
+            InputCommand::Stop => "stop".to_string(),
+            InputCommand::PonderHit => "ponderhit".to_string(),
+            InputCommand::Quit => "quit".to_string(),
+            InputCommand::Bench(args) => format!("bench {}", args),
+            InputCommand::Eval => "eval".to_string()
+        }
+    }
 }
 
 pub struct CommandResult {
@@ -18,18 +55,24 @@ pub struct CommandResult {
     pub message: String,
 }
 
-pub fn send_command(command: InputCommand) -> CommandResult {
+pub fn send_command(command: InputCommand, logger: &Logger) -> CommandResult {
     let mut message = String::from("");
     match command {
         InputCommand::Uci => {
             message.push_str(&format!("id name {} {}\n", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")));
             message.push_str(&format!("id author {}\n", env!("CARGO_PKG_AUTHORS")));
+            message.push_str(&format!("option name {} type string default <empty>\n", OPTION_DEBUG_LOG_FILE));
+            message.push_str(&format!("option name {} type spin default {} min {} max {}\n", OPTION_CONTEMPT, DEFAULT_CONTEMPT, MIN_CONTEMPT, MAX_CONTEMPT));
+            message.push_str(&format!("option name {} type check default false\n", OPTION_ANALYSIS_CONTEMPT));
+            message.push_str(&format!("option name {} type check default false\n", OPTION_ANALYSE_MODE));
+            message.push_str(&format!("option name {} type string default <empty>\n", OPTION_EVAL_FILE));
+            message.push_str(&format!("option name {} type check default false\n", OPTION_USE_NNUE));
+            message.push_str(&format!("option name {} type string default <empty>\n", OPTION_NNUE_FILE));
             message.push_str("uciok\n");
-            //todo send optional options
             CommandResult { stay: true, message }
         }
-        InputCommand::IsReady => {
-            message.push_str("readyok\n"); //wait for initializing => check game state (NOT Searching)
+        InputCommand::Debug(on) => {
+            logger.set_debug(on);
             CommandResult { stay: true, message }
         }
         InputCommand::Quit => CommandResult { stay: false, message },
@@ -0,0 +1,204 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::sync::atomic::AtomicBool;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+
+use crate::gamerecord::GameRecord;
+use crate::generator::Generator;
+use crate::global;
+use crate::hash_counter::HashCounter;
+use crate::outcome::Outcome;
+use crate::parser;
+use crate::position::Position;
+use crate::searcher::Searcher;
+use crate::searchtype::SearchType;
+
+const DEFAULT_OUTPUT: &str = "sfens.epd";
+const DEFAULT_SEARCH_TYPE: SearchType = SearchType::Depth(6);
+const DEFAULT_RANDOM_PLIES: u32 = 8;
+const DEFAULT_MAX_PLIES: u32 = 400;
+//positions with a larger score are decided and not worth recording
+const DEFAULT_MAX_SCORE: i32 = 2000;
+//random openings that end the game in a row before generation gives up
+const MAX_FAILED_OPENINGS: u32 = 1000;
+
+pub struct GenConfig {
+    pub output: String,
+    pub games: u32,
+    pub search_type: SearchType,
+    pub random_plies: u32,
+    pub max_plies: u32,
+    pub max_score: i32,
+    pub seed: u64
+}
+
+//a recorded position with the score of the side to move and the game result from white's side
+pub struct SfenEntry {
+    pub position: Position,
+    pub score: i32,
+    pub result: Outcome
+}
+
+impl SfenEntry {
+    //epd with the centipawn evaluation of the side to move and the result, as read by the tuner
+    pub fn to_epd(&self) -> String {
+        let fen = parser::get_position_fen(&self.position);
+        let epd = fen.split_whitespace().take(4).collect::<Vec<&str>>().join(" ");
+        format!("{} ce {}; c9 \"{}\";", epd, self.score, get_result_string(self.result))
+    }
+}
+
+fn get_result_string(result: Outcome) -> &'static str {
+    match result {
+        Outcome::BlackIsMate(_) => "1-0",
+        Outcome::WhiteIsMate(_) => "0-1",
+        _ => "1/2-1/2"
+    }
+}
+
+//-output <file> -games <n> -depth <n> | -nodes <n> -randomplies <n> -maxplies <n> -maxscore <cp> -seed <n>
+pub fn parse_args(args: &[String]) -> Result<GenConfig, String> {
+    let seed = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let mut config = GenConfig {
+        output: DEFAULT_OUTPUT.to_string(),
+        games: 1,
+        search_type: DEFAULT_SEARCH_TYPE,
+        random_plies: DEFAULT_RANDOM_PLIES,
+        max_plies: DEFAULT_MAX_PLIES,
+        max_score: DEFAULT_MAX_SCORE,
+        seed
+    };
+
+    let mut i = 0;
+    while i < args.len() {
+        let option = args[i].as_str();
+        let value = args.get(i + 1).ok_or(format!("{} expects one value", option))?;
+        let number = || value.parse::<u64>().map_err(|_| format!("invalid value {} for {}", value, option));
+        match option {
+            "-output" => config.output = value.to_string(),
+            "-games" => config.games = number()? as u32,
+            "-depth" => config.search_type = SearchType::Depth(number()?),
+            "-nodes" => config.search_type = SearchType::Nodes(number()?),
+            "-randomplies" => config.random_plies = number()? as u32,
+            "-maxplies" => config.max_plies = number()? as u32,
+            "-maxscore" => config.max_score = number()? as i32,
+            "-seed" => config.seed = number()?,
+            _ => return Err(format!("unknown option {}", option))
+        }
+        i += 2;
+    }
+    Ok(config)
+}
+
+//the game continues from a position reached by random moves, none if the game ended on the way
+pub fn get_random_opening(rng: &mut StdRng, plies: u32) -> Option<GameRecord> {
+    let mut record = GameRecord::new(parser::parse_startpos().expect("Error in startpos FEN"));
+    for _ in 0..plies {
+        let moves = Generator::new(record.get_position()).generate_legal_moves();
+        if moves.is_empty() {
+            return None;
+        }
+        record.apply_move(moves[rng.gen_range(0, moves.len())]);
+    }
+    match record.get_status().to_outcome(0) {
+        Some(_) => None,
+        None => Some(record)
+    }
+}
+
+//plays one game against itself, returns the result and the quiet positions it searched
+pub fn play_game(searcher: &mut Searcher, record: GameRecord, config: &GenConfig) -> (Outcome, Vec<SfenEntry>) {
+    let mut record = record;
+    let mut positions = Vec::new();
+    searcher.new_game();
+
+    let result = loop {
+        let ply = record.get_moves().len() as i32;
+        if let Some(outcome) = record.get_status().to_outcome(ply) {
+            break outcome;
+        }
+        if config.max_plies > 0 && ply as u32 >= config.max_plies {
+            break Outcome::Draw(ply, 0);
+        }
+
+        let position = *record.get_position();
+        searcher.set_position(position, record.get_history().clone());
+        let best_move = match searcher.find_best_move(config.search_type) {
+            Ok(mv) => mv,
+            Err(_) => break Outcome::Draw(ply, 0)
+        };
+
+        if let Some(Outcome::Undecided(_, value)) = searcher.get_best_score() {
+            let score = if position.get_active_color() == global::COLOR_WHITE { value } else { -value };
+            if score.abs() <= config.max_score && !best_move.is_capture() && !best_move.is_promotion() && is_quiet(searcher, &position) {
+                positions.push((position, score));
+            }
+        }
+        record.apply_move(best_move);
+    };
+
+    let entries = positions.into_iter().map(|(position, score)| SfenEntry { position, score, result }).collect();
+    (result, entries)
+}
+
+//not in check and no capture the quiescence search would play
+fn is_quiet(searcher: &mut Searcher, position: &Position) -> bool {
+    if Generator::new(position).is_check(position.get_active_color()) {
+        return false;
+    }
+    searcher.set_position(*position, HashCounter::new());
+    match searcher.quiesce() {
+        Some((_, variant)) => variant.is_empty(),
+        None => false
+    }
+}
+
+//positions are appended to the output after every game
+pub fn run(config: &GenConfig) -> Result<u64, String> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&config.output)
+        .map_err(|e| format!("cannot open {}: {}", config.output, e))?;
+
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let (_sender, receiver) = mpsc::channel();
+    let start_position = parser::parse_startpos().expect("Error in startpos FEN");
+    let mut searcher = Searcher::new(receiver, start_position, Arc::new(AtomicBool::new(false)), HashCounter::new());
+
+    let mut total = 0;
+    let mut game = 0;
+    let mut failed_openings = 0;
+    while game < config.games {
+        let record = match get_random_opening(&mut rng, config.random_plies) {
+            Some(r) => r,
+            None => {
+                failed_openings += 1;
+                if failed_openings >= MAX_FAILED_OPENINGS {
+                    return Err(format!("{} random openings in a row ended the game, try fewer random plies", failed_openings));
+                }
+                continue;
+            }
+        };
+        failed_openings = 0;
+        game += 1;
+
+        let (result, entries) = play_game(&mut searcher, record, config);
+        let mut text = String::new();
+        for entry in entries.iter() {
+            text.push_str(&entry.to_epd());
+            text.push('\n');
+        }
+        file.write_all(text.as_bytes()).map_err(|e| format!("cannot write {}: {}", config.output, e))?;
+
+        total += entries.len() as u64;
+        println!("Game {}/{}: {}, {} positions, total {}", game, config.games, get_result_string(result), entries.len(), total);
+    }
+
+    Ok(total)
+}
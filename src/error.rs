@@ -0,0 +1,118 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::global;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum HaddockError {
+    //fen
+    FenTooShort,
+    FenTooManyFields(usize),
+    BadRankCount(usize),
+    BadRankLength(u8, u32), //rank (1-8), number of squares
+    InvalidPieceChar(char),
+    InvalidActiveColor(String),
+    IllegalCastlingField(String),
+    InvalidEnPassantSquare(String),
+    InvalidHalfMoveClock(String),
+    InvalidFullMoveNumber(String),
+
+    //position legality, colors are global::COLOR_WHITE / COLOR_BLACK
+    KingMissing(u8),
+    TooManyKings(u8),
+    TooManyPawns(u8),
+    TooManyPieces(u8),
+    PawnOnBackRank(String),
+    InvalidCastlingRights(char),
+    OpponentInCheck,
+
+    //squares and moves
+    InvalidSquare(String),
+    InvalidMove(String),
+    InvalidPromotionPiece(char),
+    IllegalMove(String),
+
+    //position command
+    MissingPosition,
+    UnexpectedToken(String),
+
+    //search
+    NoLegalMove,
+
+    //evaluation parameters
+    InvalidEvalFile(String),
+    UnknownEvalParam(String),
+    EvalParamCount(String, usize, usize), //name, expected and found number of values
+    InvalidEvalParamValue(String),
+
+    //neural network evaluation
+    InvalidNetworkFile(String),
+
+    //opening and training files
+    InvalidPgn(String),
+    InvalidEpd(String),
+
+    //engine matches
+    InvalidMatchArgs(String),
+    NoOpenings,
+    UnsupportedEngineOption(String, String), //engine, option
+    InvalidEngineOptionValue(String, String), //option, value
+    MissingEngineOption(String, String), //option, the option it needs
+    EngineFailure(String),
+    CannotWriteFile(String, String) //file name, reason
+}
+
+impl fmt::Display for HaddockError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HaddockError::FenTooShort => write!(f, "fen needs at least the board and the active color"),
+            HaddockError::FenTooManyFields(n) => write!(f, "fen has {} fields, at most 6 expected", n),
+            HaddockError::BadRankCount(n) => write!(f, "fen board has {} ranks, 8 expected", n),
+            HaddockError::BadRankLength(rank, n) => write!(f, "fen rank {} has {} squares, 8 expected", rank, n),
+            HaddockError::InvalidPieceChar(c) => write!(f, "invalid piece character {}", c),
+            HaddockError::InvalidActiveColor(s) => write!(f, "invalid active color {}", s),
+            HaddockError::IllegalCastlingField(s) => write!(f, "illegal castling field {}", s),
+            HaddockError::InvalidEnPassantSquare(s) => write!(f, "invalid en passant square {}", s),
+            HaddockError::InvalidHalfMoveClock(s) => write!(f, "invalid halfmove clock {}", s),
+            HaddockError::InvalidFullMoveNumber(s) => write!(f, "invalid fullmove number {}", s),
+            HaddockError::KingMissing(color) => write!(f, "{} king missing", get_color_name(*color)),
+            HaddockError::TooManyKings(color) => write!(f, "more than one {} king", get_color_name(*color)),
+            HaddockError::TooManyPawns(color) => write!(f, "more than 8 {} pawns", get_color_name(*color)),
+            HaddockError::TooManyPieces(color) => write!(f, "too many {} pieces", get_color_name(*color)),
+            HaddockError::PawnOnBackRank(s) => write!(f, "pawn on back rank square {}", s),
+            HaddockError::InvalidCastlingRights(c) => write!(f, "castling right {} without king and rook on their squares", c),
+            HaddockError::OpponentInCheck => write!(f, "side not to move is in check"),
+            HaddockError::InvalidSquare(s) => write!(f, "invalid square {}", s),
+            HaddockError::InvalidMove(s) => write!(f, "invalid move {}", s),
+            HaddockError::InvalidPromotionPiece(c) => write!(f, "invalid promotion piece {}", c),
+            HaddockError::IllegalMove(s) => write!(f, "illegal move {}", s),
+            HaddockError::MissingPosition => write!(f, "position command needs startpos or fen"),
+            HaddockError::UnexpectedToken(s) => write!(f, "unexpected token {} in position command", s),
+            HaddockError::NoLegalMove => write!(f, "no legal move in this position"),
+            HaddockError::InvalidEvalFile(s) => write!(f, "invalid evaluation file {}", s),
+            HaddockError::UnknownEvalParam(s) => write!(f, "unknown evaluation parameter {}", s),
+            HaddockError::EvalParamCount(s, expected, found) => write!(f, "evaluation parameter {} has {} values, {} expected", s, found, expected),
+            HaddockError::InvalidEvalParamValue(s) => write!(f, "invalid evaluation parameter value {}", s),
+            HaddockError::InvalidNetworkFile(s) => write!(f, "invalid network file {}", s),
+            HaddockError::InvalidPgn(s) => write!(f, "invalid PGN {}", s),
+            HaddockError::InvalidEpd(s) => write!(f, "invalid EPD {}", s),
+            HaddockError::InvalidMatchArgs(s) => write!(f, "invalid match arguments, {}", s),
+            HaddockError::NoOpenings => write!(f, "no openings found"),
+            HaddockError::UnsupportedEngineOption(engine, option) => write!(f, "engine {} has no option {}", engine, option),
+            HaddockError::InvalidEngineOptionValue(option, value) => write!(f, "invalid value {} for option {}", value, option),
+            HaddockError::MissingEngineOption(option, needed) => write!(f, "{} needs {}", option, needed),
+            HaddockError::EngineFailure(s) => write!(f, "engine failure: {}", s),
+            HaddockError::CannotWriteFile(file_name, reason) => write!(f, "cannot write {}: {}", file_name, reason)
+        }
+    }
+}
+
+impl Error for HaddockError {}
+
+fn get_color_name(color: u8) -> &'static str {
+    if color == global::COLOR_WHITE {
+        "white"
+    } else {
+        "black"
+    }
+}
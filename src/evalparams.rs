@@ -0,0 +1,544 @@
+use std::collections::HashMap;
+use std::fs;
+
+use crate::error::HaddockError;
+use crate::score::Score;
+
+//in the order of PieceType values / 2
+const PIECE_NAMES: [&str; 6] = ["pawn", "king", "queen", "rook", "bishop", "knight"];
+
+//longer groups are written over several lines, a rank of a piece square table per line
+const TEXT_LINE_VALUES: usize = 16;
+
+//white side, middlegame and endgame table per piece
+const PIECE_SQUARE_VALUE: [[[i32; 64]; 2]; 6] = [
+    //pawn
+    [
+        [
+          0,  0,  0,  0,  0,  0,  0,  0,
+          5, 10, 10,-25,-25, 10, 10,  5,
+          0,  0,  0,  0,  0,  0,  0,  0,
+          0,  0,  0, 25, 25,  0,  0,  0,
+          0,  0, 25, 25, 25, 25,  0,  0,
+         10, 25, 25, 25, 25, 25, 25, 10,
+         50, 50, 50, 50, 50, 50, 50, 50,
+          0,  0,  0,  0,  0,  0,  0,  0
+        ],
+        [
+          0,  0,  0,  0,  0,  0,  0,  0,
+          5,  5,  5,-25,-25,  5,  5,  5,
+          0,  0,  0,  0,  0,  0,  0,  0,
+          0,  0,  0, 25, 25,  0,  0,  0,
+          0,  0, 25, 25, 25, 25,  0,  0,
+         10, 25, 25, 25, 25, 25, 25, 10,
+         50, 50, 50, 50, 50, 50, 50, 50,
+          0,  0,  0,  0,  0,  0,  0,  0
+        ]
+    ],
+    //king
+    [
+        [
+         10, 25,  0,  0,  0,  0, 25, 10,
+        -10,-10,-25,-25,-25,-25,-10,-10,
+        -50,-50,-50,-50,-50,-50,-50,-50, 
+        -50,-50,-50,-50,-50,-50,-50,-50,
+        -50,-50,-50,-50,-50,-50,-50,-50,       
+        -50,-50,-50,-50,-50,-50,-50,-50,
+        -50,-50,-50,-50,-50,-50,-50,-50,
+        -50,-50,-50,-50,-50,-50,-50,-50
+        ],
+        [
+          0,  0,  0,  0,  0,  0,  0,  0,
+          0,  0,  0,  0,  0,  0,  0,  0,
+          0,  0,  0,  0,  0,  0,  0,  0,
+          0,  0,  0,  0,  0,  0,  0,  0,
+          0,  0,  0,  0,  0,  0,  0,  0,
+          0,  0,  0,  0,  0,  0,  0,  0,
+          0,  0,  0,  0,  0,  0,  0,  0,
+          0,  0,  0,  0,  0,  0,  0,  0,
+        ]
+    ],
+    //queen
+    [
+        [
+        -50,-25,-25,  0,  0,-25,-25,-50,
+        -25,  0,  0,  0,  0,  0,  0,-25,
+        -25,  0,  0,  0,  0,  0,  0,-25,       
+        -25,  0,  0,  0,  0,  0,  0,-25,
+        -25,  0,  0,  0,  0,  0,  0,-25,
+        -25,  0,  0,  0,  0,  0,  0,-25,
+        -25,  0,  0,  0,  0,  0,  0,-25,
+        -50,-25,-25,  0,  0,-25,-25,-50
+        ],
+        [
+        -50,-25,-25,  0,  0,-25,-25,-50,
+        -25,  0,  0,  0,  0,  0,  0,-25,
+        -25,  0, 10, 25, 25, 10,  0,-25,       
+        -25,  0, 25, 25, 25, 25,  0,-25,
+        -25,  0, 25, 25, 25, 25,  0,-25,
+        -25,  0, 25, 25, 25, 25,  0,-25,
+        -25,  0,  0,  0,  0,  0,  0,-25,
+        -50,-25,-25,  0,  0,-25,-25,-50
+        ]
+    ],
+    //rook
+    [
+        [
+        -25,  0,  0, 10, 10, 10,  0,-25,
+        -25,  0,  0,  0,  0,  0,  0,-25,
+        -25,  0,  0,  0,  0,  0,  0,-25,       
+        -25,  0,  0,  0,  0,  0,  0,-25,
+        -25,  0,  0,  0,  0,  0,  0,-25,
+        -25,  0,  0,  0,  0,  0,  0,-25,
+        -25,  0,  0,  0,  0,  0,  0,-25,
+          0,  0,  0,  0,  0,  0,  0,  0,
+        ],
+        [
+          0,  0,  0,  0,  0,  0,  0,  0,
+          0,  0,  0,  0,  0,  0,  0,  0,
+          0,  0,  0,  0,  0,  0,  0,  0,
+          0,  0,  0,  0,  0,  0,  0,  0,
+          0,  0,  0,  0,  0,  0,  0,  0,
+          0,  0,  0,  0,  0,  0,  0,  0,
+          0,  0,  0,  0,  0,  0,  0,  0,
+          0,  0,  0,  0,  0,  0,  0,  0,
+        ]
+    ],
+    //bishop
+    [
+        [
+        -50,-25,-25,  0,  0,-25,-25,-50,
+        -25, 10,  0,  0,  0,  0, 10,-25,
+        -25,  0, 25,  0,  0, 25,  0,-25,       
+        -25,  0,  0, 25, 25,  0,  0,-25,
+        -25,  0,  0, 25, 25,  0,  0,-25,
+        -25,  0, 25,  0,  0, 25,  0,-25,
+        -25, 10,  0,  0,  0,  0, 10,-25,
+        -50,-25,-25,  0,  0,-25,-25,-50
+        ],
+        [
+        -50,-25,-25,  0,  0,-25,-25,-50,
+        -25, 10,  0,  0,  0,  0, 10,-25,
+        -25,  0, 10,  0,  0, 10,  0,-25,       
+        -25,  0,  0, 25, 25,  0,  0,-25,
+        -25,  0,  0, 25, 25,  0,  0,-25,
+        -25,  0, 10,  0,  0, 10,  0,-25,
+        -25, 10,  0,  0,  0,  0, 10,-25,
+        -50,-25,-25,  0,  0,-25,-25,-50
+        ]
+    ],
+    //knight
+    [
+        [
+        -50,-25,-25,-25,-25,-25,-25,-50,
+        -25,  0,  0,  0,  0,  0,  0,-25,
+        -25,  0, 25, 25, 25, 25,  0,-25,       
+        -25,  0, 25, 25, 25, 25,  0,-25,
+        -25,  0, 25, 25, 25, 25,  0,-25,
+        -25,  0, 25, 25, 25, 25,  0,-25,
+        -25,  0,  0,  0,  0,  0,  0,-25,
+        -50,-25,-25,-25,-25,-25,-25,-50
+        ],
+        [
+        -50,-25,-25,-25,-25,-25,-25,-50,
+        -25,  0,  0,  0,  0,  0,  0,-25,
+        -25,  0, 25, 25, 25, 25,  0,-25,       
+        -25,  0, 25, 25, 25, 25,  0,-25,
+        -25,  0, 25, 25, 25, 25,  0,-25,
+        -25,  0, 25, 25, 25, 25,  0,-25,
+        -25,  0,  0,  0,  0,  0,  0,-25,
+        -50,-25,-25,-25,-25,-25,-25,-50
+        ]
+    ]
+];
+
+//every weight of the evaluation, the defaults are the built in values
+#[derive(Clone, Debug, PartialEq)]
+pub struct EvalParams {
+    pub piece_value: [Score; 6],
+    pub piece_square: [[Score; 64]; 6], //white side, black uses the square mirrored vertically
+
+    //pawn structure, by relative rank where it is an array
+    pub doubled_pawn: Score,
+    pub isolated_pawn: Score,
+    pub backward_pawn: Score,
+    pub connected_pawn: [Score; 8],
+    pub passed_pawn: [Score; 8],
+    pub passed_pawn_free_path: [Score; 8],
+
+    //king safety, shield and storm by number of ranks in front of the king
+    pub pawn_shield: [Score; 3],
+    pub pawn_shield_missing: Score,
+    pub pawn_storm: [Score; 5],
+    pub open_file_near_king: Score,
+    pub semi_open_file_near_king: Score,
+    pub king_attack_weight: [i32; 6], //attack units per attacked king zone square
+    pub king_danger_divisor: i32, //danger grows with the square of the attack units
+    pub max_king_danger: i32,
+    pub king_safety_material: [i32; 6], //material of the attacker, a full set of pieces scales king safety by 1
+
+    //pieces, mobility is per reachable square above or below the usual number of reachable squares
+    pub knight_mobility: Score,
+    pub bishop_mobility: Score,
+    pub rook_mobility: Score,
+    pub queen_mobility: Score,
+    pub rook_open_file: Score,
+    pub rook_semi_open_file: Score,
+    pub rook_on_seventh: Score,
+    pub bishop_pair: Score,
+    pub knight_outpost: Score,
+    pub trapped_bishop: Score,
+    pub trapped_rook: Score,
+    pub knight_mobility_base: i32,
+    pub bishop_mobility_base: i32,
+    pub rook_mobility_base: i32,
+    pub queen_mobility_base: i32,
+    pub trapped_rook_max_mobility: i32,
+
+    //game phase weight per piece, promotions can make the total higher than at the start
+    pub phase_weight: [i32; 6]
+}
+
+pub const DEFAULT_PARAMS: EvalParams = EvalParams {
+    piece_value: [
+        Score::new(100, 100), //pawn
+        Score::new(  0,   0), //king
+        Score::new(900, 900), //queen
+        Score::new(500, 500), //rook
+        Score::new(300, 300), //bishop
+        Score::new(300, 300)  //knight
+    ],
+    piece_square: get_piece_square_scores(),
+
+    doubled_pawn: Score::new(-10, -20),
+    isolated_pawn: Score::new(-10, -15),
+    backward_pawn: Score::new(-8, -10),
+    connected_pawn: [
+        Score::new(0, 0), Score::new(3, 2), Score::new(5, 4), Score::new(8, 6),
+        Score::new(14, 12), Score::new(24, 24), Score::new(40, 40), Score::new(0, 0)
+    ],
+    passed_pawn: [
+        Score::new(0, 0), Score::new(5, 10), Score::new(5, 15), Score::new(10, 20),
+        Score::new(20, 40), Score::new(35, 70), Score::new(60, 110), Score::new(0, 0)
+    ],
+    passed_pawn_free_path: [
+        Score::new(0, 0), Score::new(0, 2), Score::new(0, 5), Score::new(2, 10),
+        Score::new(5, 20), Score::new(10, 35), Score::new(15, 60), Score::new(0, 0)
+    ],
+
+    pawn_shield: [Score::new(0, 0), Score::new(15, 0), Score::new(8, 0)],
+    pawn_shield_missing: Score::new(-15, 0),
+    pawn_storm: [Score::new(0, 0), Score::new(-5, 0), Score::new(-20, 0), Score::new(-12, 0), Score::new(-6, 0)],
+    open_file_near_king: Score::new(-20, 0),
+    semi_open_file_near_king: Score::new(-10, 0),
+    king_attack_weight: [0, 0, 5, 3, 2, 2],
+    king_danger_divisor: 4,
+    max_king_danger: 500,
+    king_safety_material: [0, 0, 9, 5, 3, 3],
+
+    knight_mobility: Score::new(4, 4),
+    bishop_mobility: Score::new(5, 5),
+    rook_mobility: Score::new(2, 4),
+    queen_mobility: Score::new(1, 2),
+    rook_open_file: Score::new(20, 10),
+    rook_semi_open_file: Score::new(10, 5),
+    rook_on_seventh: Score::new(20, 30),
+    bishop_pair: Score::new(30, 50),
+    knight_outpost: Score::new(20, 10),
+    trapped_bishop: Score::new(-80, -80),
+    trapped_rook: Score::new(-40, -10),
+    knight_mobility_base: 4,
+    bishop_mobility_base: 6,
+    rook_mobility_base: 7,
+    queen_mobility_base: 14,
+    trapped_rook_max_mobility: 3,
+
+    phase_weight: [0, 0, 4, 2, 1, 1]
+};
+
+const fn get_piece_square_scores() -> [[Score; 64]; 6] {
+    let mut result = [[Score::new(0, 0); 64]; 6];
+    let mut piece = 0;
+    while piece < 6 {
+        let mut square = 0;
+        while square < 64 {
+            result[piece][square] = Score::new(PIECE_SQUARE_VALUE[piece][0][square], PIECE_SQUARE_VALUE[piece][1][square]);
+            square += 1;
+        }
+        piece += 1;
+    }
+    result
+}
+
+//a named group of weights, scores are read and written as middlegame and endgame pairs
+pub enum ParamGroup<'a> {
+    Scores(&'a mut [Score]),
+    Values(&'a mut [i32])
+}
+
+impl<'a> ParamGroup<'a> {
+    fn get_values(&self) -> Vec<i32> {
+        match self {
+            ParamGroup::Scores(scores) => scores.iter().flat_map(|s| vec![s.mg, s.eg]).collect(),
+            ParamGroup::Values(values) => values.to_vec()
+        }
+    }
+
+    //values must have the length of get_values
+    fn set_values(&mut self, values: &[i32]) {
+        match self {
+            ParamGroup::Scores(scores) => {
+                for (i, score) in scores.iter_mut().enumerate() {
+                    *score = Score::new(values[2 * i], values[2 * i + 1]);
+                }
+            }
+            ParamGroup::Values(own) => own.copy_from_slice(values)
+        }
+    }
+}
+
+impl Default for EvalParams {
+    fn default() -> Self {
+        DEFAULT_PARAMS
+    }
+}
+
+impl EvalParams {
+    //every group with its name, in the order of the files
+    pub fn visit_mut(&mut self, f: &mut dyn FnMut(&str, ParamGroup)) {
+        f("piece_value", ParamGroup::Scores(&mut self.piece_value));
+        for (i, table) in self.piece_square.iter_mut().enumerate() {
+            f(&format!("piece_square_{}", PIECE_NAMES[i]), ParamGroup::Scores(table));
+        }
+
+        f("doubled_pawn", ParamGroup::Scores(std::slice::from_mut(&mut self.doubled_pawn)));
+        f("isolated_pawn", ParamGroup::Scores(std::slice::from_mut(&mut self.isolated_pawn)));
+        f("backward_pawn", ParamGroup::Scores(std::slice::from_mut(&mut self.backward_pawn)));
+        f("connected_pawn", ParamGroup::Scores(&mut self.connected_pawn));
+        f("passed_pawn", ParamGroup::Scores(&mut self.passed_pawn));
+        f("passed_pawn_free_path", ParamGroup::Scores(&mut self.passed_pawn_free_path));
+
+        f("pawn_shield", ParamGroup::Scores(&mut self.pawn_shield));
+        f("pawn_shield_missing", ParamGroup::Scores(std::slice::from_mut(&mut self.pawn_shield_missing)));
+        f("pawn_storm", ParamGroup::Scores(&mut self.pawn_storm));
+        f("open_file_near_king", ParamGroup::Scores(std::slice::from_mut(&mut self.open_file_near_king)));
+        f("semi_open_file_near_king", ParamGroup::Scores(std::slice::from_mut(&mut self.semi_open_file_near_king)));
+        f("king_attack_weight", ParamGroup::Values(&mut self.king_attack_weight));
+        f("king_danger_divisor", ParamGroup::Values(std::slice::from_mut(&mut self.king_danger_divisor)));
+        f("max_king_danger", ParamGroup::Values(std::slice::from_mut(&mut self.max_king_danger)));
+        f("king_safety_material", ParamGroup::Values(&mut self.king_safety_material));
+
+        f("knight_mobility", ParamGroup::Scores(std::slice::from_mut(&mut self.knight_mobility)));
+        f("bishop_mobility", ParamGroup::Scores(std::slice::from_mut(&mut self.bishop_mobility)));
+        f("rook_mobility", ParamGroup::Scores(std::slice::from_mut(&mut self.rook_mobility)));
+        f("queen_mobility", ParamGroup::Scores(std::slice::from_mut(&mut self.queen_mobility)));
+        f("rook_open_file", ParamGroup::Scores(std::slice::from_mut(&mut self.rook_open_file)));
+        f("rook_semi_open_file", ParamGroup::Scores(std::slice::from_mut(&mut self.rook_semi_open_file)));
+        f("rook_on_seventh", ParamGroup::Scores(std::slice::from_mut(&mut self.rook_on_seventh)));
+        f("bishop_pair", ParamGroup::Scores(std::slice::from_mut(&mut self.bishop_pair)));
+        f("knight_outpost", ParamGroup::Scores(std::slice::from_mut(&mut self.knight_outpost)));
+        f("trapped_bishop", ParamGroup::Scores(std::slice::from_mut(&mut self.trapped_bishop)));
+        f("trapped_rook", ParamGroup::Scores(std::slice::from_mut(&mut self.trapped_rook)));
+        f("knight_mobility_base", ParamGroup::Values(std::slice::from_mut(&mut self.knight_mobility_base)));
+        f("bishop_mobility_base", ParamGroup::Values(std::slice::from_mut(&mut self.bishop_mobility_base)));
+        f("rook_mobility_base", ParamGroup::Values(std::slice::from_mut(&mut self.rook_mobility_base)));
+        f("queen_mobility_base", ParamGroup::Values(std::slice::from_mut(&mut self.queen_mobility_base)));
+        f("trapped_rook_max_mobility", ParamGroup::Values(std::slice::from_mut(&mut self.trapped_rook_max_mobility)));
+
+        f("phase_weight", ParamGroup::Values(&mut self.phase_weight));
+    }
+
+    //names and values of all groups
+    pub fn get_groups(&self) -> Vec<(String, Vec<i32>)> {
+        let mut groups = Vec::new();
+        self.clone().visit_mut(&mut |name, group| groups.push((name.to_string(), group.get_values())));
+        groups
+    }
+
+    //all weights in one list, scores as middlegame and endgame value
+    pub fn to_values(&self) -> Vec<i32> {
+        self.get_groups().into_iter().flat_map(|(_, values)| values).collect()
+    }
+
+    //values must be a list as returned by to_values
+    pub fn set_values(&mut self, values: &[i32]) {
+        let mut start = 0;
+        self.visit_mut(&mut |_, mut group| {
+            let end = start + group.get_values().len();
+            group.set_values(&values[start..end]);
+            start = end;
+        });
+    }
+
+    //the name followed by its values, piece square tables one rank per line
+    pub fn to_text(&self) -> String {
+        let mut text = String::from("# haddock evaluation parameters, scores as middlegame endgame pairs\n");
+        for (name, values) in self.get_groups() {
+            if values.len() > TEXT_LINE_VALUES {
+                text.push_str(&format!("{}\n", name));
+                for line in values.chunks(TEXT_LINE_VALUES) {
+                    let line = line.iter().map(|v| format!("{:>4}", v)).collect::<Vec<String>>();
+                    text.push_str(&format!("   {}\n", line.join(" ")));
+                }
+            } else {
+                let values = values.iter().map(|v| v.to_string()).collect::<Vec<String>>();
+                text.push_str(&format!("{} {}\n", name, values.join(" ")));
+            }
+        }
+        text
+    }
+
+    //an object with an array of values per group
+    pub fn to_json(&self) -> String {
+        let groups = self.get_groups()
+            .into_iter()
+            .map(|(name, values)| {
+                let values = values.iter().map(|v| v.to_string()).collect::<Vec<String>>();
+                format!("  \"{}\": [{}]", name, values.join(", "))
+            })
+            .collect::<Vec<String>>();
+        format!("{{\n{}\n}}\n", groups.join(",\n"))
+    }
+
+    //text or json, groups that are not given keep their default
+    pub fn parse(text: &str) -> Result<EvalParams, HaddockError> {
+        let entries = if text.trim_start().starts_with('{') { parse_json(text)? } else { parse_text(text)? };
+        let mut entries = entries.into_iter().collect::<HashMap<String, Vec<i32>>>();
+
+        let mut params = EvalParams::default();
+        let mut result = Ok(());
+        params.visit_mut(&mut |name, mut group| {
+            if let Some(values) = entries.remove(name) {
+                let expected = group.get_values().len();
+                if values.len() == expected {
+                    group.set_values(&values);
+                } else if result.is_ok() {
+                    result = Err(HaddockError::EvalParamCount(name.to_string(), expected, values.len()));
+                }
+            }
+        });
+        result?;
+
+        match entries.keys().next() {
+            Some(name) => Err(HaddockError::UnknownEvalParam(name.clone())),
+            None => Ok(params)
+        }
+    }
+
+    pub fn load(path: &str) -> Result<EvalParams, HaddockError> {
+        let text = fs::read_to_string(path).map_err(|e| HaddockError::InvalidEvalFile(format!("{}: {}", path, e)))?;
+        EvalParams::parse(&text)
+    }
+
+    //json for files ending with .json, text otherwise
+    pub fn save(&self, path: &str) -> Result<(), HaddockError> {
+        let text = if path.ends_with(".json") { self.to_json() } else { self.to_text() };
+        fs::write(path, text).map_err(|e| HaddockError::InvalidEvalFile(format!("{}: {}", path, e)))
+    }
+}
+
+//"name value value ..." lines, lines starting with a value continue the group before them
+//# starts a comment
+fn parse_text(text: &str) -> Result<Vec<(String, Vec<i32>)>, HaddockError> {
+    let mut entries: Vec<(String, Vec<i32>)> = Vec::new();
+    for line in text.lines() {
+        let words = line.split('#').next().unwrap_or("").split_whitespace().collect::<Vec<&str>>();
+        let continued = words.first().is_some_and(|w| w.parse::<i32>().is_ok());
+        match (continued, entries.last_mut()) {
+            (true, Some((_, values))) => values.extend(parse_values(&words)?),
+            _ => {
+                if let Some((name, values)) = words.split_first() {
+                    entries.push((name.to_string(), parse_values(values)?));
+                }
+            }
+        }
+    }
+    Ok(entries)
+}
+
+fn parse_values(words: &[&str]) -> Result<Vec<i32>, HaddockError> {
+    words.iter()
+        .map(|w| w.parse::<i32>().map_err(|_| HaddockError::InvalidEvalParamValue(w.to_string())))
+        .collect()
+}
+
+//an object of numbers or (nested) arrays of numbers, nesting is flattened
+fn parse_json(text: &str) -> Result<Vec<(String, Vec<i32>)>, HaddockError> {
+    let tokens = get_json_tokens(text);
+    let mut tokens = tokens.iter().map(|t| t.as_str());
+    let unexpected = |token: Option<&str>| HaddockError::InvalidEvalFile(format!("unexpected {} in json", token.unwrap_or("end")));
+
+    match tokens.next() {
+        Some("{") => (),
+        t => return Err(unexpected(t))
+    }
+
+    let mut entries = Vec::new();
+    loop {
+        let name = match tokens.next() {
+            Some("}") if entries.is_empty() => return Ok(entries),
+            Some(t) if t.starts_with('"') => t[1..].to_string(),
+            t => return Err(unexpected(t))
+        };
+        match tokens.next() {
+            Some(":") => (),
+            t => return Err(unexpected(t))
+        }
+
+        let mut values = Vec::new();
+        let mut depth = 0;
+        loop {
+            match tokens.next() {
+                Some("[") => depth += 1,
+                Some("]") if depth > 0 => depth -= 1,
+                Some(",") if depth > 0 => (),
+                Some(t) if !t.starts_with('"') && !"{}[],:".contains(t) => values.extend(parse_values(&[t])?),
+                t => return Err(unexpected(t))
+            }
+            if depth == 0 {
+                break;
+            }
+        }
+        entries.push((name, values));
+
+        match tokens.next() {
+            Some(",") => (),
+            Some("}") => return Ok(entries),
+            t => return Err(unexpected(t))
+        }
+    }
+}
+
+//punctuation, strings with their opening quote only, and everything else up to the next separator
+fn get_json_tokens(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' | '}' | '[' | ']' | ',' | ':' => tokens.push(c.to_string()),
+            '"' => {
+                let mut token = c.to_string();
+                for c in chars.by_ref() {
+                    if c == '"' {
+                        break;
+                    }
+                    token.push(c);
+                }
+                tokens.push(token);
+            }
+            c if c.is_whitespace() => (),
+            c => {
+                let mut token = c.to_string();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || "{}[],:\"".contains(c) {
+                        break;
+                    }
+                    token.push(c);
+                    chars.next();
+                }
+                tokens.push(token);
+            }
+        }
+    }
+    tokens
+}
@@ -1,353 +1,194 @@
-use std::cmp;
 
 use crate::global::COLOR_WHITE;
 use crate::global::COLOR_BLACK;
 use crate::position::Position;
 use crate::outcome::Outcome;
 use crate::piecetype::PieceType;
+use crate::evalparams;
+use crate::evalparams::EvalParams;
+use crate::gamerecord;
+use crate::generator::Generator;
+use crate::kingsafety;
+use crate::nnue::{ Accumulator, Network };
+use crate::pawnhashtable;
+use crate::pawnhashtable::PawnHashTable;
+use crate::pawnstructure;
+use crate::pawnstructure::PawnEntry;
+use crate::pieces;
+use crate::score;
+use crate::score::Score;
 
-const PIECE_VALUE: [i32; 12] = [
-    100,-100, //pawn
-      0,   0, //king
-    900,-900, //queen
-    500,-500, //rook
-    300,-300, //bishop
-    300,-300  //knight
-];
+//evaluation with its caches and weights, owned by the searcher
+pub struct Evaluator {
+    pawn_table: PawnHashTable,
+    params: EvalParams
+}
+
+impl Default for Evaluator {
+    fn default() -> Self {
+        Evaluator::new()
+    }
+}
 
-const START_PIECE_VALUE_TOTAL:i32 = 
-    16 * PIECE_VALUE[0] + //pawn
-     4 * PIECE_VALUE[6] + //rook
-     4 * PIECE_VALUE[8] + //bishop
-     4 * PIECE_VALUE[10]; //knight
+impl Evaluator {
+    pub fn new() -> Evaluator {
+        Evaluator { pawn_table: PawnHashTable::new(pawnhashtable::DEFAULT_SIZE), params: EvalParams::default() }
+    }
+
+    pub fn clear(&mut self) {
+        self.pawn_table.clear();
+    }
 
-const END_PIECE_VALUE_THRESHOLD:i32 = 1000; //endgame threshold
-const PIECE_VALUE_DIFF:i32 = START_PIECE_VALUE_TOTAL - END_PIECE_VALUE_THRESHOLD;
+    //cached pawn scores depend on the weights, so the cache is cleared
+    pub fn set_params(&mut self, params: EvalParams) {
+        self.params = params;
+        self.pawn_table.clear();
+    }
 
-const PIECE_SQUARE_VALUE: [[[i32; 64]; 2]; 12] = [
-    //white pawn    
-    [
-        [
-          0,  0,  0,  0,  0,  0,  0,  0,
-          5, 10, 10,-25,-25, 10, 10,  5,
-          0,  0,  0,  0,  0,  0,  0,  0,
-          0,  0,  0, 25, 25,  0,  0,  0,
-          0,  0, 25, 25, 25, 25,  0,  0,
-         10, 25, 25, 25, 25, 25, 25, 10,
-         50, 50, 50, 50, 50, 50, 50, 50,
-          0,  0,  0,  0,  0,  0,  0,  0
-        ],
-        [
-          0,  0,  0,  0,  0,  0,  0,  0,
-          5,  5,  5,-25,-25,  5,  5,  5,
-          0,  0,  0,  0,  0,  0,  0,  0,
-          0,  0,  0, 25, 25,  0,  0,  0,
-          0,  0, 25, 25, 25, 25,  0,  0,
-         10, 25, 25, 25, 25, 25, 25, 10,
-         50, 50, 50, 50, 50, 50, 50, 50,
-          0,  0,  0,  0,  0,  0,  0,  0
-        ]
-    ],
-    //black pawn
-    [
-        [
-          0,  0,  0,  0,  0,  0,  0,  0,
-        -50,-50,-50,-50,-50,-50,-50,-50,
-        -10,-25,-25,-25,-25,-25,-25,-10,
-          0,  0,-25,-25,-25,-25,  0,  0,
-          0,  0,  0,-25,-25,  0,  0,  0,
-          0,  0,  0,  0,  0,  0,  0,  0,
-         -5,-10,-10, 25, 25,-10,-10, -5,
-          0,  0,  0,  0,  0,  0,  0,  0
-        ],
-        [
-          0,  0,  0,  0,  0,  0,  0,  0,
-        -50,-50,-50,-50,-50,-50,-50,-50,
-        -10,-25,-25,-25,-25,-25,-25,-10,
-          0,  0,-25,-25,-25,-25,  0,  0,
-          0,  0,  0,-25,-25,  0,  0,  0,
-          0,  0,  0,  0,  0,  0,  0,  0,
-         -5, -5, -5, 25, 25, -5, -5, -5,
-          0,  0,  0,  0,  0,  0,  0,  0
-        ]
-    ],
-    //white king
-    [
-        [
-         10, 25,  0,  0,  0,  0, 25, 10,
-        -10,-10,-25,-25,-25,-25,-10,-10,
-        -50,-50,-50,-50,-50,-50,-50,-50, 
-        -50,-50,-50,-50,-50,-50,-50,-50,
-        -50,-50,-50,-50,-50,-50,-50,-50,       
-        -50,-50,-50,-50,-50,-50,-50,-50,
-        -50,-50,-50,-50,-50,-50,-50,-50,
-        -50,-50,-50,-50,-50,-50,-50,-50
-        ],
-        [
-          0,  0,  0,  0,  0,  0,  0,  0,
-          0,  0,  0,  0,  0,  0,  0,  0,
-          0,  0,  0,  0,  0,  0,  0,  0,
-          0,  0,  0,  0,  0,  0,  0,  0,
-          0,  0,  0,  0,  0,  0,  0,  0,
-          0,  0,  0,  0,  0,  0,  0,  0,
-          0,  0,  0,  0,  0,  0,  0,  0,
-          0,  0,  0,  0,  0,  0,  0,  0,
-        ]
-    ],
-    //black king
-    [
-        [
-         50, 50, 50, 50, 50, 50, 50, 50, 
-         50, 50, 50, 50, 50, 50, 50, 50,
-         50, 50, 50, 50, 50, 50, 50, 50,       
-         50, 50, 50, 50, 50, 50, 50, 50,
-         50, 50, 50, 50, 50, 50, 50, 50,
-         50, 50, 50, 50, 50, 50, 50, 50,
-         10, 10, 25, 25, 25, 25, 10, 10,
-        -10,-25,  0,  0,  0,  0,-25,-10
-        ],
-        [
-          0,  0,  0,  0,  0,  0,  0,  0,
-          0,  0,  0,  0,  0,  0,  0,  0,
-          0,  0,  0,  0,  0,  0,  0,  0,
-          0,  0,  0,  0,  0,  0,  0,  0,
-          0,  0,  0,  0,  0,  0,  0,  0,
-          0,  0,  0,  0,  0,  0,  0,  0,
-          0,  0,  0,  0,  0,  0,  0,  0,
-          0,  0,  0,  0,  0,  0,  0,  0,
-        ]
-    ],
-    //white queen
-    [
-        [
-        -50,-25,-25,  0,  0,-25,-25,-50,
-        -25,  0,  0,  0,  0,  0,  0,-25,
-        -25,  0,  0,  0,  0,  0,  0,-25,       
-        -25,  0,  0,  0,  0,  0,  0,-25,
-        -25,  0,  0,  0,  0,  0,  0,-25,
-        -25,  0,  0,  0,  0,  0,  0,-25,
-        -25,  0,  0,  0,  0,  0,  0,-25,
-        -50,-25,-25,  0,  0,-25,-25,-50
-        ],
-        [
-        -50,-25,-25,  0,  0,-25,-25,-50,
-        -25,  0,  0,  0,  0,  0,  0,-25,
-        -25,  0, 10, 25, 25, 10,  0,-25,       
-        -25,  0, 25, 25, 25, 25,  0,-25,
-        -25,  0, 25, 25, 25, 25,  0,-25,
-        -25,  0, 25, 25, 25, 25,  0,-25,
-        -25,  0,  0,  0,  0,  0,  0,-25,
-        -50,-25,-25,  0,  0,-25,-25,-50
-        ]
-    ],
-    //black queen
-    [
-        [
-        50, 25, 25,  0,  0, 25, 25, 50,
-        25,  0,  0,  0,  0,  0,  0, 25,
-        25,  0,  0,  0,  0,  0,  0, 25,       
-        25,  0,  0,  0,  0,  0,  0, 25,
-        25,  0,  0,  0,  0,  0,  0, 25,
-        25,  0,  0,  0,  0,  0,  0, 25,
-        25,  0,  0,  0,  0,  0,  0, 25,
-        50, 25, 25,  0,  0, 25, 25, 50
-        ],
-        [
-        50, 25, 25,  0,  0, 25, 25, 50,
-        25,  0,  0,  0,  0,  0,  0, 25,
-        25,  0,-25,-25,-25,-25,  0, 25,       
-        25,  0,-25,-25,-25,-25,  0, 25,
-        25,  0,-25,-25,-25,-25,  0, 25,
-        25,  0,-10,-25,-25,-10,  0, 25,
-        25,  0,  0,  0,  0,  0,  0, 25,
-        50, 25, 25,  0,  0, 25, 25, 50
-        ]
-    ],
-    //white rook
-    [
-        [
-        -25,  0,  0, 10, 10, 10,  0,-25,
-        -25,  0,  0,  0,  0,  0,  0,-25,
-        -25,  0,  0,  0,  0,  0,  0,-25,       
-        -25,  0,  0,  0,  0,  0,  0,-25,
-        -25,  0,  0,  0,  0,  0,  0,-25,
-        -25,  0,  0,  0,  0,  0,  0,-25,
-        -25,  0,  0,  0,  0,  0,  0,-25,
-          0,  0,  0,  0,  0,  0,  0,  0,
-        ],
-        [
-          0,  0,  0,  0,  0,  0,  0,  0,
-          0,  0,  0,  0,  0,  0,  0,  0,
-          0,  0,  0,  0,  0,  0,  0,  0,
-          0,  0,  0,  0,  0,  0,  0,  0,
-          0,  0,  0,  0,  0,  0,  0,  0,
-          0,  0,  0,  0,  0,  0,  0,  0,
-          0,  0,  0,  0,  0,  0,  0,  0,
-          0,  0,  0,  0,  0,  0,  0,  0,
-        ]
-    ],
-    //black rook
-    [
-        [
-          0,  0,  0,  0,  0,  0,  0,  0,
-         25,  0,  0,  0,  0,  0,  0, 25,
-         25,  0,  0,  0,  0,  0,  0, 25,       
-         25,  0,  0,  0,  0,  0,  0, 25,
-         25,  0,  0,  0,  0,  0,  0, 25,
-         25,  0,  0,  0,  0,  0,  0, 25,
-         25,  0,  0,  0,  0,  0,  0, 25,
-         25,  0,  0,-10,-10,-10,  0, 25,
-        ],
-        [
-          0,  0,  0,  0,  0,  0,  0,  0,
-          0,  0,  0,  0,  0,  0,  0,  0,
-          0,  0,  0,  0,  0,  0,  0,  0,
-          0,  0,  0,  0,  0,  0,  0,  0,
-          0,  0,  0,  0,  0,  0,  0,  0,
-          0,  0,  0,  0,  0,  0,  0,  0,
-          0,  0,  0,  0,  0,  0,  0,  0,
-          0,  0,  0,  0,  0,  0,  0,  0,
-        ]
-    ],
-    //white bishop
-    [
-        [
-        -50,-25,-25,  0,  0,-25,-25,-50,
-        -25, 10,  0,  0,  0,  0, 10,-25,
-        -25,  0, 25,  0,  0, 25,  0,-25,       
-        -25,  0,  0, 25, 25,  0,  0,-25,
-        -25,  0,  0, 25, 25,  0,  0,-25,
-        -25,  0, 25,  0,  0, 25,  0,-25,
-        -25, 10,  0,  0,  0,  0, 10,-25,
-        -50,-25,-25,  0,  0,-25,-25,-50
-        ],
-        [
-        -50,-25,-25,  0,  0,-25,-25,-50,
-        -25, 10,  0,  0,  0,  0, 10,-25,
-        -25,  0, 10,  0,  0, 10,  0,-25,       
-        -25,  0,  0, 25, 25,  0,  0,-25,
-        -25,  0,  0, 25, 25,  0,  0,-25,
-        -25,  0, 10,  0,  0, 10,  0,-25,
-        -25, 10,  0,  0,  0,  0, 10,-25,
-        -50,-25,-25,  0,  0,-25,-25,-50
-        ]
-    ],
-    //black bishop
-    [
-        [
-        50, 25, 25,  0,  0, 25, 25, 50,
-        25,-10,  0,  0,  0,  0,-10, 25,
-        25,  0,-10,  0,  0,-10,  0, 25,       
-        25,  0,  0,-25,-25,  0,  0, 25,
-        25,  0,  0,-25,-25,  0,  0, 25,
-        25,  0,-10,  0,  0,-10,  0, 25,
-        25,-10,  0,  0,  0,  0,-10, 25,
-        50, 25, 25,  0,  0, 25, 25, 50
-        ],
-        [
-        50, 25, 25,  0,  0, 25, 25, 50,
-        25,-10,  0,  0,  0,  0,-10, 25,
-        25,  0,-10,  0,  0,-10,  0, 25,
-        25,  0,  0,-25,-25,  0,  0, 25,
-        25,  0,  0,-25,-25,  0,  0, 25,
-        25,  0,-10,  0,  0,-10,  0, 25,
-        25,-10,  0,  0,  0,  0,-10, 25,
-        50, 25, 25,  0,  0, 25, 25, 50
-        ]
-    ],
-    //white knight
-    [
-        [
-        -50,-25,-25,-25,-25,-25,-25,-50,
-        -25,  0,  0,  0,  0,  0,  0,-25,
-        -25,  0, 25, 25, 25, 25,  0,-25,       
-        -25,  0, 25, 25, 25, 25,  0,-25,
-        -25,  0, 25, 25, 25, 25,  0,-25,
-        -25,  0, 25, 25, 25, 25,  0,-25,
-        -25,  0,  0,  0,  0,  0,  0,-25,
-        -50,-25,-25,-25,-25,-25,-25,-50
-        ],
-        [
-        -50,-25,-25,-25,-25,-25,-25,-50,
-        -25,  0,  0,  0,  0,  0,  0,-25,
-        -25,  0, 25, 25, 25, 25,  0,-25,       
-        -25,  0, 25, 25, 25, 25,  0,-25,
-        -25,  0, 25, 25, 25, 25,  0,-25,
-        -25,  0, 25, 25, 25, 25,  0,-25,
-        -25,  0,  0,  0,  0,  0,  0,-25,
-        -50,-25,-25,-25,-25,-25,-25,-50
-        ]
-    ],
-    //black knight
-    [
-        [
-         50, 25, 25, 25, 25, 25, 25, 50,
-         25,  0,  0,  0,  0,  0,  0, 25,
-         25,  0,-25,-25,-25,-25,  0, 25,
-         25,  0,-25,-25,-25,-25,  0, 25,
-         25,  0,-25,-25,-25,-25,  0, 25,       
-         25,  0,-25,-25,-25,-25,  0, 25,
-         25,  0,  0,  0,  0,  0,  0, 25,
-         50, 25, 25, 25, 25, 25, 25, 50
-        ],
-        [
-         50, 25, 25, 25, 25, 25, 25, 50,
-         25,  0,  0,  0,  0,  0,  0, 25,
-         25,  0,-25,-25,-25,-25,  0, 25,
-         25,  0,-25,-25,-25,-25,  0, 25,
-         25,  0,-25,-25,-25,-25,  0, 25,       
-         25,  0,-25,-25,-25,-25,  0, 25,
-         25,  0,  0,  0,  0,  0,  0, 25,
-         50, 25, 25, 25, 25, 25, 25, 50
-        ]
-    ]
-];
+    pub fn evaluate(&mut self, position: &Position, depth: i32) -> Outcome {
+        let pawn_entry = match self.pawn_table.get(position.get_pawn_hash()) {
+            Some(e) => e,
+            None => {
+                let e = pawnstructure::evaluate_pawns(position, &self.params);
+                self.pawn_table.insert(position.get_pawn_hash(), e);
+                e
+            }
+        };
+        evaluate_with_pawns(position, depth, &pawn_entry, &self.params)
+    }
+}
 
+//without cache, with the default weights, used by the benchmarks and tests
+#[allow(dead_code)]
 pub fn evaluate(position: &Position, depth: i32) -> Outcome {
-    //TODO not enough material ??
+    evaluate_with_params(position, depth, &evalparams::DEFAULT_PARAMS)
+}
 
-    let material_value = get_material_value(position);
-    Outcome::Undecided(depth, material_value)
+//without cache
+pub fn evaluate_with_params(position: &Position, depth: i32, params: &EvalParams) -> Outcome {
+    evaluate_with_pawns(position, depth, &pawnstructure::evaluate_pawns(position, params), params)
 }
 
-fn get_material_value(position: &Position) -> i32 {
-    let mut value: i32 = 0;
-    let pos_value_total = get_pos_value_total(position);
+//draws are recognized as with the hand crafted evaluation
+pub fn evaluate_network(position: &Position, depth: i32, network: &Network, accumulator: &Accumulator) -> Outcome {
+    if gamerecord::is_insufficient_material(position) || gamerecord::is_dead_position(position) {
+        return Outcome::Draw(depth, 0);
+    }
 
-    //anything lower than 1000 points is endgame
-    let start_weight = cmp::min(pos_value_total - END_PIECE_VALUE_THRESHOLD, 0); //distance to threshold
-    let end_weight = cmp::max(START_PIECE_VALUE_TOTAL - pos_value_total, PIECE_VALUE_DIFF); //distance to start
+    let value = network.evaluate(accumulator, position.get_active_color());
+    Outcome::Undecided(depth, if position.get_active_color() == COLOR_WHITE { value } else { -value })
+}
 
+fn evaluate_with_pawns(position: &Position, depth: i32, pawn_entry: &PawnEntry, params: &EvalParams) -> Outcome {
+    if gamerecord::is_insufficient_material(position) || gamerecord::is_dead_position(position) {
+        return Outcome::Draw(depth, 0);
+    }
 
-    for (piece, square) in position.get_all_active_pieces() {
-        value += PIECE_VALUE[piece.to_usize()] + 
-        (
-            PIECE_SQUARE_VALUE[piece.to_usize()][0][square.to_usize()] * start_weight +
-            PIECE_SQUARE_VALUE[piece.to_usize()][1][square.to_usize()] * end_weight
-        ) / PIECE_VALUE_DIFF;
+    let score =
+        get_material_score(position, params) +
+        pawn_entry.score +
+        pawnstructure::evaluate_passed_pawns(position, pawn_entry, params) +
+        kingsafety::evaluate_king_safety(position, params) +
+        pieces::evaluate_pieces(position, params);
+    Outcome::Undecided(depth, score.taper(get_phase(position, params)))
+}
+
+//from score::PHASE_MAX with all pieces on the board to 0 with only kings and pawns
+pub fn get_phase(position: &Position, params: &EvalParams) -> i32 {
+    let mut phase = 0;
+    for piece_value in 0..12 {
+        phase += position.get_piece_count(PieceType::new(piece_value)) * params.phase_weight[(piece_value / 2) as usize];
     }
+    let total = get_phase_total(params);
+    phase.clamp(0, total) * score::PHASE_MAX / total
+}
+
+//both sides with queen, two rooks, two bishops and two knights, at least 1 as the weights can be tuned to 0
+fn get_phase_total(params: &EvalParams) -> i32 {
+    let weight = &params.phase_weight;
+    (2 * (weight[2] + 2 * (weight[3] + weight[4] + weight[5]))).max(1)
+}
 
-    //println!("pos total {} material value is {}", pos_value_total, value);
+//material and piece square tables
+fn get_material_score(position: &Position, params: &EvalParams) -> Score {
+    get_material_color(position, COLOR_WHITE, params) + get_piece_square_color(position, COLOR_WHITE, params) -
+        get_material_color(position, COLOR_BLACK, params) - get_piece_square_color(position, COLOR_BLACK, params)
+}
+
+fn get_material_color(position: &Position, color: u8, params: &EvalParams) -> Score {
+    let mut score = Score::default();
+    for piece_value in (color..12).step_by(2) {
+        let piece = PieceType::new(piece_value);
+        score += params.piece_value[(piece_value >> 1) as usize] * position.get_piece_count(piece);
+    }
+    score
+}
 
-    value
+//black uses the table of white with the square mirrored vertically
+fn get_piece_square_color(position: &Position, color: u8, params: &EvalParams) -> Score {
+    let mut score = Score::default();
+    for piece_value in (color..12).step_by(2) {
+        let index = (piece_value >> 1) as usize;
+        for square in position.get_bit_board(PieceType::new(piece_value)).get_squares() {
+            let square = if color == COLOR_WHITE { square.to_usize() } else { square.to_usize() ^ 56 };
+            score += params.piece_square[index][square];
+        }
+    }
+    score
 }
 
-fn get_pos_value_total(position: &Position) -> i32 {
-    return
+//every term of the evaluation for both colors, for the eval command
+pub fn trace(position: &Position, params: &EvalParams) -> String {
+    let generator = Generator::new(position);
+    let pawn_entry = pawnstructure::evaluate_pawns(position, params);
+    let terms: Vec<(&str, Score, Score)> = vec![
+        ("Material", get_material_color(position, COLOR_WHITE, params), get_material_color(position, COLOR_BLACK, params)),
+        ("Piece squares", get_piece_square_color(position, COLOR_WHITE, params), get_piece_square_color(position, COLOR_BLACK, params)),
         (
-            position.get_piece_count(PieceType::new_pawn(COLOR_WHITE)) + 
-            position.get_piece_count(PieceType::new_pawn(COLOR_BLACK))
-        ) * PIECE_VALUE[PieceType::new_pawn(COLOR_WHITE).to_usize()] +
+            "Pawn structure",
+            pawnstructure::evaluate_color(position, COLOR_WHITE, params),
+            pawnstructure::evaluate_color(position, COLOR_BLACK, params)
+        ),
         (
-            position.get_piece_count(PieceType::new_rook(COLOR_WHITE)) + 
-            position.get_piece_count(PieceType::new_rook(COLOR_BLACK))
-        ) * PIECE_VALUE[PieceType::new_rook(COLOR_WHITE).to_usize()] +
+            "Free passers",
+            pawnstructure::evaluate_passed_pawns_color(position, &pawn_entry, COLOR_WHITE, params),
+            pawnstructure::evaluate_passed_pawns_color(position, &pawn_entry, COLOR_BLACK, params)
+        ),
         (
-            position.get_piece_count(PieceType::new_bishop(COLOR_WHITE)) + 
-            position.get_piece_count(PieceType::new_bishop(COLOR_BLACK))
-        ) * PIECE_VALUE[PieceType::new_bishop(COLOR_WHITE).to_usize()] +
+            "King safety",
+            kingsafety::evaluate_color(position, &generator, COLOR_WHITE, params),
+            kingsafety::evaluate_color(position, &generator, COLOR_BLACK, params)
+        ),
         (
-            position.get_piece_count(PieceType::new_knight(COLOR_WHITE)) + 
-            position.get_piece_count(PieceType::new_knight(COLOR_BLACK))
-        ) * PIECE_VALUE[PieceType::new_knight(COLOR_WHITE).to_usize()];
-}
\ No newline at end of file
+            "Pieces",
+            pieces::evaluate_color(position, &generator, COLOR_WHITE, params),
+            pieces::evaluate_color(position, &generator, COLOR_BLACK, params)
+        )
+    ];
+
+    let mut result = format!("{}\n", position);
+    result.push_str("          Term |    White    |    Black    |    Total\n");
+    result.push_str("               |   MG    EG  |   MG    EG  |   MG    EG\n");
+    result.push_str("---------------+-------------+-------------+-------------\n");
+    let mut total = [Score::default(); 2];
+    for (name, white, black) in terms {
+        total[0] += white;
+        total[1] += black;
+        result.push_str(&format_trace_line(name, white, black));
+    }
+    result.push_str("---------------+-------------+-------------+-------------\n");
+    result.push_str(&format_trace_line("Total", total[0], total[1]));
+
+    let phase = get_phase(position, params);
+    result.push_str(&format!("\nPhase: {} of {}\n", phase, score::PHASE_MAX));
+    match evaluate_with_params(position, 0, params) {
+        Outcome::Draw(_, _) => result.push_str("Evaluation: draw, no mate possible\n"),
+        outcome => result.push_str(&format!("Evaluation: {} (white side)\n", outcome.score()))
+    }
+    result
+}
+
+fn format_trace_line(name: &str, white: Score, black: Score) -> String {
+    let total = white - black;
+    format!("{:>14} | {:>5} {:>5} | {:>5} {:>5} | {:>5} {:>5}\n", name, white.mg, white.eg, black.mg, black.eg, total.mg, total.eg)
+}
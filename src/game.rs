@@ -5,33 +5,70 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use crate::searchtype::SearchType;
+use crate::command;
 use crate::command::InputCommand;
 use crate::parser;
-use crate::position::Position;
+use crate::positioncommand;
+use crate::gamerecord::GameRecord;
 use crate::searchcommand::SearchCommand;
 use crate::searcher::Searcher;
-use crate::move_::Move_;
-use crate::generator::Generator;
 use crate::hash_counter::HashCounter;
+use crate::bench;
+use crate::evalparams::EvalParams;
+use crate::evaluation;
+use crate::nnue::Network;
+use crate::uci;
+use crate::logger::Logger;
+use crate::reporter::UciReporter;
 
 pub struct Game {
     receiver: Receiver<InputCommand>,
-    position: Option<Position>,
+    game_record: Option<GameRecord>,
     searcher_handle: Option<thread::JoinHandle<()>>,
-    searcher_channel: Option<Sender<SearchCommand>>,
+    searcher_channel: Sender<SearchCommand>,
+    searcher_finished: Receiver<()>,
+    searching: bool,
     stop_signal: Arc<AtomicBool>,
-    history: HashCounter,
+    logger: Logger,
+    contempt: i32,
+    analysis_contempt: bool,
+    analyse_mode: bool,
+    eval_params: EvalParams,
+    use_nnue: bool,
+    network: Option<Arc<Network>>
 }
 
 impl<'a> Game {
-    pub fn new(receiver: Receiver<InputCommand>) -> Game {
+    pub fn new(receiver: Receiver<InputCommand>, logger: Logger) -> Game {
+        let (sender_request, receiver_request): (Sender<SearchCommand>, Receiver<SearchCommand>) = mpsc::channel();
+        let (sender_finished, receiver_finished): (Sender<()>, Receiver<()>) = mpsc::channel();
+        let stop_signal = Arc::new(AtomicBool::new(false));
+
+        //the searcher thread lives as long as the game, so its tables are kept between moves
+        let start_position = parser::parse_startpos().expect("Error in startpos FEN");
+        let stop_signal_clone = stop_signal.clone();
+        let logger_clone = logger.clone();
+        let searcher_handle = thread::spawn(move || {
+            let mut searcher = Searcher::new(receiver_request, start_position, stop_signal_clone, HashCounter::new());
+            searcher.set_reporter(Box::new(UciReporter::new(logger_clone)));
+            searcher.start(&sender_finished);
+        });
+
         Game {
             receiver,
-            position: None,
-            searcher_handle: None,
-            searcher_channel: None,
-            stop_signal: Arc::new(AtomicBool::new(false)),
-            history: HashCounter::new(),
+            game_record: None,
+            searcher_handle: Some(searcher_handle),
+            searcher_channel: sender_request,
+            searcher_finished: receiver_finished,
+            searching: false,
+            stop_signal,
+            logger,
+            contempt: command::DEFAULT_CONTEMPT,
+            analysis_contempt: false,
+            analyse_mode: false,
+            eval_params: EvalParams::default(),
+            use_nnue: false,
+            network: None
         }
     }
 
@@ -52,16 +89,24 @@ impl<'a> Game {
             InputCommand::Stop => self.handle_command_stop(),
             InputCommand::Position(args) => self.handle_command_position(&args),
             InputCommand::Go(args) => self.handle_command_go(&args),
+            InputCommand::Bench(args) => self.handle_command_bench(args),
+            InputCommand::Eval => self.handle_command_eval(),
+            InputCommand::IsReady => self.handle_command_isready(),
+            InputCommand::SetOption(args) => self.handle_command_setoption(args),
+            InputCommand::UciNewGame => self.handle_command_ucinewgame(),
             _ => {
-                println!("handle_command received other command");
+                debug_log!(self.logger, "handle_command received other command");
                 true
             }
         }
     }
 
     fn handle_command_quit(&mut self) -> bool {
-        self.stop_signal.store(true, Ordering::Relaxed);
-        self.cleanup_searcher();
+        self.stop_search();
+        self.searcher_channel.send(SearchCommand::Quit).expect("Error while sending search command");
+        if let Some(sh) = self.searcher_handle.take() {
+            sh.join().expect("Error while synchronizing with search thread");
+        }
         false
     }
 
@@ -70,76 +115,143 @@ impl<'a> Game {
         true
     }
 
-    fn handle_command_position(&mut self, args: &str) -> bool {
-        self.position = None;
+    //searching happens in its own thread, so this is answered right away during search
+    //otherwise it waits till the searcher thread has done the commands sent before, like clearing tables
+    fn handle_command_isready(&mut self) -> bool {
+        if self.searching && self.searcher_finished.try_recv().is_ok() {
+            self.searching = false;
+        }
+        if !self.searching {
+            let (sender, receiver) = mpsc::channel();
+            self.searcher_channel.send(SearchCommand::Ping(sender)).expect("Error while sending search command");
+            receiver.recv().expect("Error while synchronizing with search thread");
+        }
+        self.logger.send("readyok");
+        true
+    }
 
-        let args_parts = args.split(" ").collect::<Vec<&str>>();
+    fn handle_command_ucinewgame(&mut self) -> bool {
+        self.stop_search();
 
-        let mut i: usize = 0;
-        while i < args_parts.len() {
-            match args_parts[i] {
-                "startpos" => match parser::parse_startpos() {
-                    Some(position) => {
-                        self.position = Some(position);
-                        self.history.clear();
-                    }
-                    None => {
-                        println!("Error in startpos FEN");
-                        return true;
+        self.game_record = None;
+        self.searcher_channel.send(SearchCommand::NewGame).expect("Error while sending search command");
+        true
+    }
+
+    fn handle_command_setoption(&mut self, args: &str) -> bool {
+        match uci::parse_setoption(args) {
+            Some((name, value)) => {
+                if name.eq_ignore_ascii_case(command::OPTION_DEBUG_LOG_FILE) {
+                    if let Err(e) = self.logger.set_log_file(&value) {
+                        self.logger.send(&format!("info string {}", e));
                     }
-                },
-                "fen" => {
-                    let max_fen_index;
-                    match args_parts.iter().position(|&r| r == "moves") {
-                        None => max_fen_index = args_parts.len(),
-                        Some(i) => max_fen_index = i
+                } else if name.eq_ignore_ascii_case(command::OPTION_CONTEMPT) {
+                    match value.parse::<i32>() {
+                        Ok(n) => self.contempt = n.max(command::MIN_CONTEMPT).min(command::MAX_CONTEMPT),
+                        Err(_) => self.logger.send(&format!("info string invalid value {} for {}", value, name))
                     }
+                    self.send_contempt();
+                } else if name.eq_ignore_ascii_case(command::OPTION_ANALYSIS_CONTEMPT) {
+                    self.analysis_contempt = value.eq_ignore_ascii_case("true");
+                    self.send_contempt();
+                } else if name.eq_ignore_ascii_case(command::OPTION_ANALYSE_MODE) {
+                    self.analyse_mode = value.eq_ignore_ascii_case("true");
+                    self.send_contempt();
+                } else if name.eq_ignore_ascii_case(command::OPTION_EVAL_FILE) {
+                    self.load_eval_file(&value);
+                } else if name.eq_ignore_ascii_case(command::OPTION_USE_NNUE) {
+                    self.use_nnue = value.eq_ignore_ascii_case("true");
+                    self.send_network();
+                } else if name.eq_ignore_ascii_case(command::OPTION_NNUE_FILE) {
+                    self.load_network_file(&value);
+                }
+            }
+            None => self.logger.send("info string invalid setoption command")
+        }
+        true
+    }
 
-                    if max_fen_index < 2 {
-                        println!("FEN too short");
-                        return true;
-                    }
+    //the searcher handles commands in order, a running search keeps its contempt
+    fn send_contempt(&mut self) {
+        let contempt = if self.analyse_mode && !self.analysis_contempt { 0 } else { self.contempt };
+        self.searcher_channel.send(SearchCommand::SetContempt(contempt)).expect("Error while sending search command");
+    }
 
-                    match parser::parse_fen(&args_parts[1..max_fen_index]) {
-                        Some(position) => {
-                            self.position = Some(position);
-                            self.history.clear();
-                            i += max_fen_index;
-                        }
-                        None => {
-                            println!("Error in FEN");
-                            return true;
-                        }
-                    }
+    //no file means the built in weights, on errors the previous weights are kept
+    fn load_eval_file(&mut self, path: &str) {
+        let params = if path.is_empty() || path == "<empty>" {
+            EvalParams::default()
+        } else {
+            match EvalParams::load(path) {
+                Ok(p) => p,
+                Err(e) => {
+                    self.logger.send(&format!("info string {}", e));
+                    return;
                 }
-                "moves" => (),
-                _ => match &mut self.position {
-                    Some(pos) => {
-                        match Move_::from_str(args_parts[i]) {
-                            Some(mv) => {
-                                let mv = pos.analyze_move(mv);
-                                if Generator::new(pos).is_legal_move(mv) {
-                                    //todo check 3 fold repetition here
-                                    self.history.incr(pos.get_hash());
-                                    pos.apply_move(mv);
-                                } else {
-                                    println!("{} is an illegal move!", &args_parts[i]);
-                                    println!("internal position:\n{}", pos);
-                                    return true;
-                                }
-                            }
-                            None => {
-                                println!("Error in move");
-                                return true;
-                            }
-                        }
-                    }
-                    None => return true
+            }
+        };
+        self.eval_params = params.clone();
+        self.searcher_channel.send(SearchCommand::SetEvalParams(Box::new(params))).expect("Error while sending search command");
+    }
+
+    //no file unloads the network, on errors the previous network is kept
+    fn load_network_file(&mut self, path: &str) {
+        if path.is_empty() || path == "<empty>" {
+            self.network = None;
+        } else {
+            match Network::load(path) {
+                Ok(n) => self.network = Some(Arc::new(n)),
+                Err(e) => {
+                    self.logger.send(&format!("info string {}", e));
+                    return;
                 }
             }
-            i += 1;
         }
+        self.send_network();
+    }
+
+    //the hand crafted evaluation is used while no network is loaded
+    fn send_network(&mut self) {
+        if self.use_nnue && self.network.is_none() {
+            self.logger.send(&format!("info string no network loaded, set {} to use it", command::OPTION_NNUE_FILE));
+        }
+        let network = if self.use_nnue { self.network.clone() } else { None };
+        self.searcher_channel.send(SearchCommand::SetNetwork(network)).expect("Error while sending search command");
+    }
+
+    //on errors the previous position is kept
+    fn handle_command_position(&mut self, args: &str) -> bool {
+        match positioncommand::parse(args) {
+            Ok(record) => self.game_record = Some(record),
+            Err(e) => self.logger.send(&format!("info string {}", e))
+        }
+        true
+    }
+
+    fn handle_command_bench(&mut self, args: &str) -> bool {
+        //bench runs its own searches, make sure the current one is finished
+        self.stop_search();
+
+        match bench::get_depth(args) {
+            Some(depth) => bench::print_result(&bench::run(depth, &self.logger), &self.logger),
+            None => self.logger.send("info string invalid bench depth")
+        }
+        true
+    }
 
+    //evaluation terms of the current position, the start position if none is set
+    fn handle_command_eval(&mut self) -> bool {
+        let position = match &self.game_record {
+            Some(r) => *r.get_position(),
+            None => parser::parse_startpos().expect("Error in startpos FEN")
+        };
+        for line in evaluation::trace(&position, &self.eval_params).lines() {
+            self.logger.send(line);
+        }
+        if let (true, Some(network)) = (self.use_nnue, &self.network) {
+            let outcome = evaluation::evaluate_network(&position, 0, network, &network.get_accumulator(&position));
+            self.logger.send(&format!("NNUE evaluation: {} (white side)", outcome.score()));
+        }
         true
     }
 
@@ -156,7 +268,7 @@ impl<'a> Game {
                 }
                 "depth" => {
                     i += 1;
-                    let (succeeded, value) = Game::get_numeric_value(&args_parts, i);
+                    let (succeeded, value) = self.get_numeric_value(&args_parts, i);
                     if !succeeded {
                         return true;
                     }
@@ -164,7 +276,7 @@ impl<'a> Game {
                 }
                 "nodes" => {
                     i += 1;
-                    let (succeeded, value) = Game::get_numeric_value(&args_parts, i);
+                    let (succeeded, value) = self.get_numeric_value(&args_parts, i);
                     if !succeeded {
                         return true;
                     }
@@ -172,7 +284,7 @@ impl<'a> Game {
                 }
                 "movetime" => {
                     i += 1;
-                    let (succeeded, value) = Game::get_numeric_value(&args_parts, i);
+                    let (succeeded, value) = self.get_numeric_value(&args_parts, i);
                     if !succeeded {
                         return true;
                     }
@@ -180,7 +292,7 @@ impl<'a> Game {
                 }
                 "wtime" => {
                     i += 1;
-                    let (succeeded, value) = Game::get_numeric_value(&args_parts, i);
+                    let (succeeded, value) = self.get_numeric_value(&args_parts, i);
                     if !succeeded {
                         return true;
                     }
@@ -192,7 +304,7 @@ impl<'a> Game {
                 }
                 "btime" => {
                     i += 1;
-                    let (succeeded, value) = Game::get_numeric_value(&args_parts, i);
+                    let (succeeded, value) = self.get_numeric_value(&args_parts, i);
                     if !succeeded {
                         return true;
                     }
@@ -204,7 +316,7 @@ impl<'a> Game {
                 }
                 "winc" => {
                     i += 1;
-                    let (succeeded, value) = Game::get_numeric_value(&args_parts, i);
+                    let (succeeded, value) = self.get_numeric_value(&args_parts, i);
                     if !succeeded {
                         return true;
                     }
@@ -216,7 +328,7 @@ impl<'a> Game {
                 }
                 "binc" => {
                     i += 1;
-                    let (succeeded, value) = Game::get_numeric_value(&args_parts, i);
+                    let (succeeded, value) = self.get_numeric_value(&args_parts, i);
                     if !succeeded {
                         return true;
                     }
@@ -228,7 +340,7 @@ impl<'a> Game {
                 }
                 "movestogo" => {
                     i += 1;
-                    let (succeeded, value) = Game::get_numeric_value(&args_parts, i);
+                    let (succeeded, value) = self.get_numeric_value(&args_parts, i);
                     if !succeeded {
                         return true;
                     }
@@ -244,21 +356,12 @@ impl<'a> Game {
         }
 
 
-        let sender: &Sender<SearchCommand>;
-        self.setup_search();
-
-        match &self.searcher_channel {
-            Some(s) => sender = s,
-            None => return true
-        }
-
-        sender.send(SearchCommand::FindBestMove(search_type)).
-            expect("Error while sending search command");
+        self.start_search(search_type);
 
         true
     }
 
-    fn get_numeric_value(args_parts: &Vec<&str>, i: usize) -> (bool, u64) {
+    fn get_numeric_value(&self, args_parts: &Vec<&str>, i: usize) -> (bool, u64) {
         if args_parts.len() < i {
             return (false, 0);
         }
@@ -267,51 +370,36 @@ impl<'a> Game {
                 (true, n)
             }
             Err(_) => {
-                println!("Could not parse number");
+                debug_log!(self.logger, "Could not parse number");
                 (false, 0)
             }
         }
     }
 
-    fn setup_search(&mut self) {
-        //cleanup current search, if needed
-        self.stop_signal.store(false, Ordering::Relaxed);
-        self.cleanup_searcher();
-
-        let (sender_request, receiver_request): (Sender<SearchCommand>, Receiver<SearchCommand>) = mpsc::channel();
-
-        let position_clone: Position;
+    fn start_search(&mut self, search_type: SearchType) {
+        //a running search is stopped first
+        self.stop_search();
 
-        match &self.position {
-            Some(p) => position_clone = p.clone(),
+        let (position, history) = match &self.game_record {
+            Some(r) => (*r.get_position(), r.get_history().clone()),
             None => {
-                println!("Cannot setup search without position");
+                debug_log!(self.logger, "Cannot setup search without position");
                 return;
             }
-        }
-
-        let stop_signal_clone = self.stop_signal.clone();
-        let history_clone = self.history.clone();
-
-        self.searcher_handle = Some(thread::spawn(move || {
-            let mut searcher = Searcher::new(receiver_request, position_clone, stop_signal_clone, history_clone);
-            searcher.start();
-        }));
+        };
 
-        self.searcher_channel = Some(sender_request);
+        self.stop_signal.store(false, Ordering::Relaxed);
+        self.searcher_channel.send(SearchCommand::FindBestMove(position, history, search_type)).
+            expect("Error while sending search command");
+        self.searching = true;
     }
 
-    fn cleanup_searcher(&mut self) {
-        match &self.searcher_channel {
-            Some(sc) => {
-                sc.send(SearchCommand::Quit).expect("Error while sending search command");
-                if let Some(sh) = self.searcher_handle.take() {
-                    sh.join().expect("Error while synchronizing with search thread");
-                    self.searcher_handle = None;
-                    self.searcher_channel = None
-                }
-            }
-            None => ()
+    //stops the current search and waits till its best move is sent
+    fn stop_search(&mut self) {
+        if self.searching {
+            self.stop_signal.store(true, Ordering::Relaxed);
+            self.searcher_finished.recv().expect("Error while synchronizing with search thread");
+            self.searching = false;
         }
     }
 }
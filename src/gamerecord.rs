@@ -0,0 +1,171 @@
+use std::fmt;
+
+use crate::generator::Generator;
+use crate::global;
+use crate::hash_counter::HashCounter;
+use crate::move_::Move_;
+use crate::outcome::Outcome;
+use crate::piecetype::PieceType;
+use crate::position::Position;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum GameStatus {
+    Ongoing,
+    Checkmate(u8), //color of the mated side
+    Stalemate,
+    FivefoldRepetition,
+    SeventyFiveMoveRule,
+    InsufficientMaterial,
+    DeadPosition,
+    //these draws have to be claimed by a player
+    ThreefoldRepetition,
+    FiftyMoveRule
+}
+
+impl GameStatus {
+    //outcome with the given ply, none while the game goes on
+    pub fn to_outcome(self, ply: i32) -> Option<Outcome> {
+        match self {
+            GameStatus::Ongoing => None,
+            GameStatus::Checkmate(global::COLOR_WHITE) => Some(Outcome::WhiteIsMate(ply)),
+            GameStatus::Checkmate(_) => Some(Outcome::BlackIsMate(ply)),
+            _ => Some(Outcome::Draw(ply, 0))
+        }
+    }
+}
+
+impl fmt::Display for GameStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self {
+            GameStatus::Ongoing => "ongoing",
+            GameStatus::Checkmate(_) => "checkmate",
+            GameStatus::Stalemate => "stalemate",
+            GameStatus::FivefoldRepetition => "fivefold repetition",
+            GameStatus::SeventyFiveMoveRule => "seventy-five move rule",
+            GameStatus::InsufficientMaterial => "insufficient material",
+            GameStatus::DeadPosition => "dead position",
+            GameStatus::ThreefoldRepetition => "threefold repetition",
+            GameStatus::FiftyMoveRule => "fifty move rule"
+        };
+        write!(f, "{}", text)
+    }
+}
+
+//all moves of a game with the positions before them, the last position is the current one
+#[derive(Clone)]
+pub struct GameRecord {
+    positions: Vec<Position>,
+    moves: Vec<Move_>,
+    history: HashCounter
+}
+
+impl GameRecord {
+    pub fn new(start_position: Position) -> GameRecord {
+        GameRecord { positions: vec![start_position], moves: Vec::new(), history: HashCounter::new() }
+    }
+
+    //the move must be legal and analyzed
+    pub fn apply_move(&mut self, move_: Move_) {
+        let mut position = *self.get_position();
+        self.history.incr(position.get_hash());
+        position.apply_move(move_);
+        self.positions.push(position);
+        self.moves.push(move_);
+    }
+
+    pub fn get_position(&self) -> &Position {
+        self.positions.last().unwrap()
+    }
+
+    #[allow(dead_code)]
+    pub fn get_positions(&self) -> &[Position] {
+        &self.positions
+    }
+
+    pub fn get_moves(&self) -> &[Move_] {
+        &self.moves
+    }
+
+    //hashes of all positions before the current one, as used by the searcher for repetitions
+    pub fn get_history(&self) -> &HashCounter {
+        &self.history
+    }
+
+    //number of times the current position occurred, including now
+    pub fn get_repetition_count(&self) -> i32 {
+        self.history.get(self.get_position().get_hash()) + 1
+    }
+
+    //automatic endings go before draws that have to be claimed, mate goes before everything
+    pub fn get_status(&self) -> GameStatus {
+        let position = self.get_position();
+        let generator = Generator::new(position);
+        let active_color = position.get_active_color();
+
+        if generator.generate_legal_moves().is_empty() {
+            if generator.is_check(active_color) {
+                return GameStatus::Checkmate(active_color);
+            }
+            return GameStatus::Stalemate;
+        }
+
+        let repetitions = self.get_repetition_count();
+        if repetitions >= 5 {
+            return GameStatus::FivefoldRepetition;
+        }
+        if position.get_halfmoveclock() >= global::SEVENTY_FIVE_MOVE_RULE_PLIES {
+            return GameStatus::SeventyFiveMoveRule;
+        }
+        if is_insufficient_material(position) {
+            return GameStatus::InsufficientMaterial;
+        }
+        if is_dead_position(position) {
+            return GameStatus::DeadPosition;
+        }
+        if repetitions >= 3 {
+            return GameStatus::ThreefoldRepetition;
+        }
+        if position.get_halfmoveclock() >= global::FIFTY_MOVE_RULE_PLIES {
+            return GameStatus::FiftyMoveRule;
+        }
+
+        GameStatus::Ongoing
+    }
+}
+
+//only kings, or kings and a single minor piece
+pub fn is_insufficient_material(position: &Position) -> bool {
+    let mut minor_count = 0;
+    for color in 0..2 {
+        if has_pawns_rooks_or_queens(position, color) {
+            return false;
+        }
+        minor_count +=
+            position.get_piece_count(PieceType::new_bishop(color)) +
+            position.get_piece_count(PieceType::new_knight(color));
+    }
+    minor_count <= 1
+}
+
+//no mate is possible with any sequence of moves, only the basic case:
+//kings and bishops, all bishops on squares of the same color
+pub fn is_dead_position(position: &Position) -> bool {
+    let mut square_colors = [false; 2];
+    for color in 0..2 {
+        if has_pawns_rooks_or_queens(position, color) ||
+            position.get_piece_count(PieceType::new_knight(color)) > 0 {
+            return false;
+        }
+        for square in position.get_bit_board(PieceType::new_bishop(color)).get_squares() {
+            let (x, y) = square.to_xy();
+            square_colors[((x + y) % 2) as usize] = true;
+        }
+    }
+    !(square_colors[0] && square_colors[1])
+}
+
+fn has_pawns_rooks_or_queens(position: &Position, color: u8) -> bool {
+    position.get_piece_count(PieceType::new_pawn(color)) > 0 ||
+        position.get_piece_count(PieceType::new_rook(color)) > 0 ||
+        position.get_piece_count(PieceType::new_queen(color)) > 0
+}
@@ -100,6 +100,22 @@ impl<'a> Generator<'a> {
         MoveResult::Next(pos)
     }
 
+    pub fn generate_legal_moves(&self) -> Vec<Move_> {
+        let color = self.position.get_active_color();
+        let mut result = self.generate_moves(false);
+
+        result.retain(|&mv| {
+            if mv.is_castling() && !self.is_castling_legal(mv) {
+                return false;
+            }
+            let mut pos = *self.position;
+            pos.apply_move(mv);
+            !Generator::new(&pos).is_check(color)
+        });
+
+        result
+    }
+
     pub fn generate_moves(&self, only_captures: bool) -> Vec<Move_> {
         let mut result: Vec<Move_> = Vec::with_capacity(80);
 
@@ -230,6 +246,45 @@ impl<'a> Generator<'a> {
         move_board
     }
 
+    //squares attacked by a piece on the square, sliders stop at the first piece of any color
+    pub fn get_attack_board(&self, square: Square, piece_type: PieceType) -> BitBoard {
+        match piece_type.get_type() {
+            piecetype::PIECE_PAWN => moveboard::get_move_board(PAWN_CAP_MOVEBOARD[piece_type.get_color() as usize], square),
+            piecetype::PIECE_KING => moveboard::get_move_board(moveboard::MOVEBOARD_KING, square),
+            piecetype::PIECE_KNIGHT => moveboard::get_move_board(moveboard::MOVEBOARD_KNIGHT, square),
+            piecetype::PIECE_ROOK => self.get_orthogonal_attack_board(square),
+            piecetype::PIECE_BISHOP => self.get_diagonal_attack_board(square),
+            _ => self.get_orthogonal_attack_board(square) | self.get_diagonal_attack_board(square)
+        }
+    }
+
+    fn get_orthogonal_attack_board(&self, square: Square) -> BitBoard {
+        self.get_ray_attack_board(moveboard::DIR_UP, square, BitBoard::get_lowest_square) |
+        self.get_ray_attack_board(moveboard::DIR_RIGHT, square, BitBoard::get_lowest_square) |
+        self.get_ray_attack_board(moveboard::DIR_DOWN, square, BitBoard::get_highest_square) |
+        self.get_ray_attack_board(moveboard::DIR_LEFT, square, BitBoard::get_highest_square)
+    }
+
+    fn get_diagonal_attack_board(&self, square: Square) -> BitBoard {
+        self.get_ray_attack_board(moveboard::DIR_UP_RIGHT, square, BitBoard::get_lowest_square) |
+        self.get_ray_attack_board(moveboard::DIR_DOWN_RIGHT, square, BitBoard::get_highest_square) |
+        self.get_ray_attack_board(moveboard::DIR_DOWN_LEFT, square, BitBoard::get_highest_square) |
+        self.get_ray_attack_board(moveboard::DIR_UP_LEFT, square, BitBoard::get_lowest_square)
+    }
+
+    //like generate_move_board, but the blocking piece is included whatever its color
+    fn get_ray_attack_board(&self, direction: usize, current_square: Square, get_nearest: fn(BitBoard) -> Square) -> BitBoard {
+        let forward_ray_board = moveboard::get_ray_board(direction, current_square);
+        let inter = forward_ray_board & self.all_piece_board;
+        if inter.not_empty() {
+            let nearest_square = get_nearest(inter);
+            let reverse_ray_board = moveboard::get_ray_board((direction + 4) % 8, nearest_square);
+            (forward_ray_board & reverse_ray_board) | BitBoard::from_square(nearest_square)
+        } else {
+            forward_ray_board
+        }
+    }
+
     pub fn generate_king_moves(&self, current_square: Square, only_captures: bool, moves: &mut Vec<Move_>) {
         self.generate_moveboard_moves(current_square, moveboard::MOVEBOARD_KING, only_captures, moves);
 
@@ -1,5 +1,7 @@
 pub const FEN_STARTPOS: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
-pub const MAX_HALFMOVECLOCK: u32 = 50;
+//halfmove clock limits, in plies
+pub const FIFTY_MOVE_RULE_PLIES: u32 = 100;
+pub const SEVENTY_FIVE_MOVE_RULE_PLIES: u32 = 150;
 
 pub const COLOR_WHITE: u8 = 0;
 pub const COLOR_BLACK: u8 = 1;
\ No newline at end of file
@@ -18,7 +18,8 @@ impl HashCounter {
         *self.0.entry(hash).or_insert(0) += 1;
     }
 
-    pub fn decr(&mut self, hash: u64) {
+    //returns false if the hash was not counted
+    pub fn decr(&mut self, hash: u64) -> bool {
         match self.0.get_mut(&hash) {
             Some(c) => {
                 if *c == 1 {
@@ -27,10 +28,9 @@ impl HashCounter {
                 else {
                     *c -= 1;
                 }
+                true
             },
-            None => {
-                println!("hash {:X} not found while decr", hash);
-            }
+            None => false
         }
     }
 
@@ -0,0 +1,106 @@
+use crate::bitboard::BitBoard;
+use crate::evalparams::EvalParams;
+use crate::generator::Generator;
+use crate::global::{COLOR_WHITE, COLOR_BLACK};
+use crate::moveboard;
+use crate::piecetype::PieceType;
+use crate::position::Position;
+use crate::score::Score;
+use crate::square::Square;
+
+//from white's side
+pub fn evaluate_king_safety(position: &Position, params: &EvalParams) -> Score {
+    let generator = Generator::new(position);
+    evaluate_color(position, &generator, COLOR_WHITE, params) - evaluate_color(position, &generator, COLOR_BLACK, params)
+}
+
+//score from the side of the given color, fades when the opponent has less material
+pub fn evaluate_color(position: &Position, generator: &Generator, color: u8, params: &EvalParams) -> Score {
+    let king_square = position.get_king_square(color);
+    let score =
+        evaluate_pawn_cover(position, color, king_square, params) +
+        evaluate_attacks(position, generator, color, king_square, params);
+
+    let other_color = 1 - color;
+    let mut material = 0;
+    for piece_value in (PieceType::new_queen(other_color).to_u8()..12).step_by(2) {
+        material += position.get_piece_count(PieceType::new(piece_value)) * params.king_safety_material[(piece_value / 2) as usize];
+    }
+    let full_material = get_full_material(params);
+    let material = material.clamp(0, full_material);
+
+    Score::new(score.mg * material / full_material, score.eg * material / full_material)
+}
+
+//queen, two rooks, two bishops and two knights, at least 1 as the weights can be tuned to 0
+fn get_full_material(params: &EvalParams) -> i32 {
+    let weight = &params.king_safety_material;
+    (weight[2] + 2 * (weight[3] + weight[4] + weight[5])).max(1)
+}
+
+//pawn shield, pawn storm and open files, on the king file and the files next to it
+fn evaluate_pawn_cover(position: &Position, color: u8, king_square: Square, params: &EvalParams) -> Score {
+    let own_pawns = position.get_bit_board(PieceType::new_pawn(color));
+    let enemy_pawns = position.get_bit_board(PieceType::new_pawn(1 - color));
+    let (king_x, king_y) = king_square.to_xy();
+    let mut score = Score::default();
+
+    for x in king_x.saturating_sub(1)..=std::cmp::min(king_x + 1, 7) {
+        let file = BitBoard::from_file(x);
+
+        match get_nearest_in_front(own_pawns & file, color, king_y) {
+            Some(distance) if distance < params.pawn_shield.len() => score += params.pawn_shield[distance],
+            _ => score += params.pawn_shield_missing
+        }
+        if let Some(distance) = get_nearest_in_front(enemy_pawns & file, color, king_y) {
+            if distance < params.pawn_storm.len() {
+                score += params.pawn_storm[distance];
+            }
+        }
+
+        if (own_pawns & file).get_count() == 0 {
+            if (enemy_pawns & file).get_count() == 0 {
+                score += params.open_file_near_king;
+            } else {
+                score += params.semi_open_file_near_king;
+            }
+        }
+    }
+    score
+}
+
+//number of ranks between the king and the nearest pawn in front of it
+fn get_nearest_in_front(pawns: BitBoard, color: u8, king_y: u8) -> Option<usize> {
+    pawns.get_squares()
+        .iter()
+        .map(|s| s.to_xy().1 as i32 - king_y as i32)
+        .map(|d| if color == COLOR_WHITE { d } else { -d })
+        .filter(|&d| d > 0)
+        .min()
+        .map(|d| d as usize)
+}
+
+//attack units of enemy pieces hitting the squares around the king, counted from two attackers on
+fn evaluate_attacks(position: &Position, generator: &Generator, color: u8, king_square: Square, params: &EvalParams) -> Score {
+    let king_zone = moveboard::get_move_board(moveboard::MOVEBOARD_KING, king_square) | BitBoard::from_square(king_square);
+    let other_color = 1 - color;
+    let mut attackers = 0;
+    let mut units = 0;
+
+    for piece_value in (PieceType::new_queen(other_color).to_u8()..12).step_by(2) {
+        let piece = PieceType::new(piece_value);
+        for square in position.get_bit_board(piece).get_squares() {
+            let hits = (generator.get_attack_board(square, piece) & king_zone).get_count() as i32;
+            if hits > 0 {
+                attackers += 1;
+                units += hits * params.king_attack_weight[(piece_value / 2) as usize];
+            }
+        }
+    }
+
+    if attackers < 2 {
+        return Score::default();
+    }
+    let danger = std::cmp::min(units * units / params.king_danger_divisor.max(1), params.max_king_danger);
+    Score::new(-danger, -danger / 8)
+}
@@ -8,6 +8,7 @@ extern crate lazy_static;
 pub mod macros;
 
 pub mod global;
+pub mod error;
 pub mod hash_key_hasher;
 pub mod zobrist;
 pub mod hash_counter;
@@ -29,5 +30,29 @@ pub mod evaluation;
 pub mod bitboard;
 pub mod moveboard;
 pub mod transposition_table;
+pub mod bench;
+pub mod san;
+pub mod pgn;
+pub mod sprt;
+pub mod player;
+pub mod matchrunner;
+pub mod uciclient;
+pub mod searchevent;
+pub mod reporter;
+pub mod logger;
+pub mod uciengine;
+pub mod positioncommand;
+pub mod validation;
+pub mod gamerecord;
+pub mod score;
+pub mod pawnstructure;
+pub mod pawnhashtable;
+pub mod kingsafety;
+pub mod pieces;
+pub mod evalparams;
+pub mod tuner;
+pub mod datagen;
+pub mod nnue;
+pub mod trainer;
 
 mod tests;
\ No newline at end of file
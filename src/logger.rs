@@ -0,0 +1,97 @@
+use std::fs::File;
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const DIRECTION_INPUT: &str = ">>";
+pub const DIRECTION_OUTPUT: &str = "<<";
+
+//all uci output goes through here, so it can be mirrored into the debug log file
+//diagnostics are only sent as info string when the gui switched debug on
+struct LoggerState {
+    output: Box<dyn Write + Send>,
+    debug: bool,
+    log_file: Option<File>
+}
+
+//cheap to clone, all clones share the same output
+#[derive(Clone)]
+pub struct Logger(Arc<Mutex<LoggerState>>);
+
+impl Logger {
+    pub fn new(output: Box<dyn Write + Send>) -> Logger {
+        Logger(Arc::new(Mutex::new(LoggerState { output, debug: false, log_file: None })))
+    }
+
+    pub fn stdout() -> Logger {
+        Logger::new(Box::new(io::stdout()))
+    }
+
+    pub fn set_debug(&self, debug: bool) {
+        self.0.lock().unwrap().debug = debug;
+    }
+
+    pub fn is_debug(&self) -> bool {
+        self.0.lock().unwrap().debug
+    }
+
+    //an empty path (or the uci <empty> placeholder) closes the log file
+    pub fn set_log_file(&self, path: &str) -> Result<(), String> {
+        let mut state = self.0.lock().unwrap();
+        if path.is_empty() || path == "<empty>" {
+            state.log_file = None;
+            return Ok(());
+        }
+
+        match File::create(path) {
+            Ok(f) => {
+                state.log_file = Some(f);
+                Ok(())
+            }
+            Err(e) => {
+                state.log_file = None;
+                Err(format!("cannot open log file {}: {}", path, e))
+            }
+        }
+    }
+
+    //line received from the gui
+    pub fn log_input(&self, line: &str) {
+        let mut state = self.0.lock().unwrap();
+        state.write_log_line(DIRECTION_INPUT, line.trim_end());
+    }
+
+    //line sent to the gui
+    pub fn send(&self, line: &str) {
+        let mut state = self.0.lock().unwrap();
+        let _ = writeln!(state.output, "{}", line).and_then(|_| state.output.flush());
+        state.write_log_line(DIRECTION_OUTPUT, line);
+    }
+
+    //diagnostic message, only visible in debug mode
+    pub fn debug(&self, message: &str) {
+        if self.is_debug() {
+            for line in message.lines() {
+                self.send(&format!("info string {}", line));
+            }
+        }
+    }
+}
+
+impl LoggerState {
+    fn write_log_line(&mut self, direction: &str, line: &str) {
+        if let Some(f) = &mut self.log_file {
+            let _ = writeln!(f, "{}", format_log_line(SystemTime::now(), direction, line));
+        }
+    }
+}
+
+//e.g. "13:45:12.345 >> go depth 5", time in utc
+pub fn format_log_line(time: SystemTime, direction: &str, line: &str) -> String {
+    let millis = time.duration_since(UNIX_EPOCH).map(|d| d.as_millis()).unwrap_or(0);
+    let seconds_of_day = (millis / 1000) % 86400;
+    format!(
+        "{:02}:{:02}:{:02}.{:03} {} {}",
+        seconds_of_day / 3600, (seconds_of_day / 60) % 60, seconds_of_day % 60, millis % 1000, direction, line
+    )
+}
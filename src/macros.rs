@@ -7,4 +7,13 @@ macro_rules! hashmap {
     }}
 }
 
-//useage: hashmap!{1 => 'A', 2 => 'b'}
\ No newline at end of file
+//useage: hashmap!{1 => 'A', 2 => 'b'}
+
+//diagnostic output, sent as info string only when uci debug is on
+//useage: debug_log!(logger, "value {}", 1)
+#[macro_export]
+macro_rules! debug_log {
+    ($logger: expr, $($arg: tt)*) => {
+        $logger.debug(&format!($($arg)*))
+    }
+}
@@ -7,6 +7,7 @@ extern crate lazy_static;
 mod macros;
 
 mod global;
+mod error;
 mod hash_key_hasher;
 mod zobrist;
 mod hash_counter;
@@ -28,110 +29,94 @@ mod evaluation;
 mod bitboard;
 mod moveboard;
 mod transposition_table;
-
+mod bench;
+mod san;
+mod pgn;
+mod sprt;
+mod player;
+mod matchrunner;
+mod uciclient;
+mod searchevent;
+mod reporter;
+mod logger;
+mod uciengine;
+mod positioncommand;
+mod validation;
+mod gamerecord;
+mod score;
+mod pawnstructure;
+mod pawnhashtable;
+mod kingsafety;
+mod pieces;
+mod evalparams;
+mod tuner;
+mod datagen;
+mod nnue;
+mod trainer;
+
+use std::env;
 use std::io;
-use std::sync::mpsc::{Sender, Receiver};
-use std::sync::mpsc;
-use std::thread;
-use crate::command::InputCommand;
-use crate::game::Game;
+use std::process;
+use crate::logger::Logger;
+use crate::uciengine::UciEngine;
 
 fn main() {
-    println!("Welcome to Haddock Chess Engine");
-
-    //initialize game...
-    let (game_handle, game_channel) = setup_game();
-    let (_console_handle, input_channel) = setup_input();
-
-    println!("Please input UCI command");
-
-    //main loop to receive commands
-    loop {
-        let line: String;
-        match input_channel.recv() {
-            Ok(s) => line = s,
-            Err(e) => {
-                println!("Error while receiving input : {}", e);
-                break;
-            }
+    //command line tools run and exit, without a tool the engine speaks uci
+    let args: Vec<String> = env::args().collect();
+    let result = match args.get(1).map(String::as_str) {
+        Some("bench") => run_bench(&args[2..]),
+        Some("match") => matchrunner::parse_args(&args[2..])
+            .and_then(|config| matchrunner::run(&config).map(|_| ()))
+            .map_err(|e| e.to_string()),
+        Some("tune") => tuner::parse_args(&args[2..])
+            .map_err(|e| format!("invalid arguments, {}", e))
+            .and_then(|config| tuner::run(&config).map(|_| ())),
+        Some("gensfens") => datagen::parse_args(&args[2..])
+            .map_err(|e| format!("invalid arguments, {}", e))
+            .and_then(|config| datagen::run(&config).map(|_| ())),
+        Some("train") => trainer::parse_args(&args[2..])
+            .map_err(|e| format!("invalid arguments, {}", e))
+            .and_then(|config| trainer::run(&config).map(|_| ())),
+        Some("evalparams") => write_evalparams(args.get(2)),
+        _ => {
+            run_uci();
+            return;
         }
+    };
 
-        let command = uci::parse(&line);
-
-        let stay = match command {
-            Some(InputCommand::Quit) => {
-                false
-            }
-            Some(InputCommand::Stop) => {
-                game_channel.send(InputCommand::Stop).expect("Error sending command");
-                true
-            }
-            Some(InputCommand::Position(args)) => {
-                game_channel.send(InputCommand::Position(args)).expect("Error sending command");
-                true
-            }
-            Some(InputCommand::Go(args)) => {
-                game_channel.send(InputCommand::Go(args)).expect("Error sending command");
-                true
-            }
-            Some(c) => {
-                let result = command::send_command(c);
-                if !result.message.is_empty() {
-                    print!("{}", &result.message)
-                }
-                result.stay
-            }
-            None => {
-                println!("info string unknown command");
-                true
-            }
-        };
-
-        if !stay {
-            break;
-        }
+    //exit status 1, so scripts can tell a failed run
+    if let Err(e) = result {
+        eprintln!("Error in {}: {}", args[1], e);
+        process::exit(1);
     }
-
-    // wait till game thread finished
-    game_channel.send(InputCommand::Quit).expect("Error cleaning up");
-    game_handle.join().unwrap();
-
-    //terminate console thread how??
-    println!("Goodbye");
 }
 
-fn setup_game() -> (thread::JoinHandle<()>, Sender<InputCommand>) {
-    let (sender, receiver): (Sender<InputCommand>, Receiver<InputCommand>) = mpsc::channel();
-
-    let game_handle = thread::spawn(move || {
-        let mut game = Game::new(receiver);
-        game.start();
-    });
-
-    (game_handle, sender)
+fn run_bench(args: &[String]) -> Result<(), String> {
+    let logger = Logger::stdout();
+    let depth = bench::get_depth(&args.join(" ")).ok_or("invalid bench depth")?;
+    bench::print_result(&bench::run(depth, &logger), &logger);
+    Ok(())
 }
 
-fn setup_input() -> (thread::JoinHandle<()>, Receiver<String>) {
-    let (sender, receiver): (Sender<String>, Receiver<String>) = mpsc::channel();
-
-    //input via console
-    //this process has to clean itself up, read_line is a blocking command
-    let sender_clone = sender.clone();
-    let console_handle = thread::spawn(move || {
-        loop {
-            let mut line = String::new();
-            io::stdin().read_line(&mut line).expect("Error reading line from console");
-            sender_clone.send(line).expect("Error sending string over channel");
+//the built in evaluation weights as a starting point for an EvalFile, to stdout without a path
+fn write_evalparams(path: Option<&String>) -> Result<(), String> {
+    match path {
+        Some(path) => evalparams::EvalParams::default().save(path).map_err(|e| e.to_string()),
+        None => {
+            print!("{}", evalparams::EvalParams::default().to_text());
+            Ok(())
         }
-    });
+    }
+}
 
-    //input ctrl c
+fn run_uci() {
+    //input ctrl c, stdout only carries uci output
     //NOTE under cargo, this doesn't work properly, but it's working fine when run directly
-    let sender_clone = sender.clone();
     ctrlc::set_handler(move || {
-        println!("ctrl-c received");
-        sender_clone.send("quit".to_string()).expect("Error sending string over channel");
+        process::exit(0);
     }).expect("Error setting ctrl-c handler");
 
-    (console_handle, receiver)
-}
\ No newline at end of file
+    let mut engine = UciEngine::new(Box::new(io::stdout()));
+    let stdin = io::stdin();
+    engine.run(stdin.lock());
+}
@@ -0,0 +1,368 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+
+use crate::error::HaddockError;
+use crate::gamerecord::GameRecord;
+use crate::generator::Generator;
+use crate::global;
+use crate::move_::Move_;
+use crate::outcome::Outcome;
+use crate::parser;
+use crate::pgn;
+use crate::pgn::{Opening, PgnGame};
+use crate::player::{Player, SearcherPlayer, UciProcessPlayer};
+use crate::position::Position;
+use crate::searchtype::SearchType;
+use crate::sprt::{MatchStats, Sprt, SprtResult};
+
+pub const ENGINE_INTERNAL: &str = "internal";
+const DEFAULT_SEARCH_TYPE: SearchType = SearchType::Depth(4);
+
+pub struct EngineConfig {
+    pub name: Option<String>,
+    pub command: String,
+    pub search_type: SearchType,
+    pub options: Vec<(String, String)>
+}
+
+pub struct MatchConfig {
+    pub engines: Vec<EngineConfig>,
+    pub games: u32,
+    pub openings: Option<String>,
+    pub pgn_file: Option<String>,
+    pub sprt: Option<Sprt>,
+    pub max_plies: u32
+}
+
+pub struct GameResult {
+    pub outcome: Outcome,
+    pub termination: String,
+    pub moves: Vec<Move_>
+}
+
+impl GameResult {
+    pub fn to_pgn_result(&self) -> String {
+        match self.outcome {
+            Outcome::WhiteIsMate(_) => "0-1".to_string(),
+            Outcome::BlackIsMate(_) => "1-0".to_string(),
+            Outcome::Draw(_, _) => "1/2-1/2".to_string(),
+            Outcome::Undecided(_, _) => "*".to_string()
+        }
+    }
+}
+
+impl EngineConfig {
+    fn new() -> EngineConfig {
+        EngineConfig { name: None, command: ENGINE_INTERNAL.to_string(), search_type: DEFAULT_SEARCH_TYPE, options: Vec::new() }
+    }
+
+    fn create_player(&self) -> Result<Box<dyn Player>, HaddockError> {
+        let mut player: Box<dyn Player> = if self.command == ENGINE_INTERNAL {
+            Box::new(SearcherPlayer::with_options(ENGINE_INTERNAL, self.search_type, &self.options)?)
+        } else {
+            Box::new(UciProcessPlayer::new(&self.command, self.search_type, &self.options)?)
+        };
+
+        if let Some(name) = &self.name {
+            player = Box::new(NamedPlayer { name: name.clone(), player });
+        }
+        Ok(player)
+    }
+}
+
+//overrides the name reported by the engine
+struct NamedPlayer {
+    name: String,
+    player: Box<dyn Player>
+}
+
+impl Player for NamedPlayer {
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn new_game(&mut self) -> Result<(), HaddockError> {
+        self.player.new_game()
+    }
+
+    fn find_best_move(&mut self, fen: &str, moves: &[Move_]) -> Result<Option<Move_>, HaddockError> {
+        self.player.find_best_move(fen, moves)
+    }
+}
+
+//cutechess-like arguments:
+//-engine cmd=<internal|path> [name=..] [depth=..|nodes=..|movetime=..] [option.<name>=<value>]
+//  (at least twice, with more engines the first one plays a gauntlet against the others)
+//-each <same keys as engine> -games <n> -openings <file.epd|file.pgn> -pgnout <file>
+//-sprt elo0=<elo> elo1=<elo> alpha=<a> beta=<b> -maxplies <n>
+pub fn parse_args(args: &[String]) -> Result<MatchConfig, HaddockError> {
+    let mut config = MatchConfig {
+        engines: Vec::new(),
+        games: 2,
+        openings: None,
+        pgn_file: None,
+        sprt: None,
+        max_plies: 0
+    };
+    let mut each: Vec<(String, String)> = Vec::new();
+
+    let mut i = 0;
+    while i < args.len() {
+        let option = args[i].as_str();
+        let mut values: Vec<&str> = Vec::new();
+        i += 1;
+        while i < args.len() && !args[i].starts_with('-') {
+            values.push(&args[i]);
+            i += 1;
+        }
+
+        match option {
+            "-engine" => {
+                let mut engine = EngineConfig::new();
+                for (key, value) in get_key_values(&values)? {
+                    apply_engine_key(&mut engine, &key, &value)?;
+                }
+                config.engines.push(engine);
+            }
+            "-each" => each.append(&mut get_key_values(&values)?),
+            "-games" => config.games = get_single_value(option, &values)?,
+            "-maxplies" => config.max_plies = get_single_value(option, &values)?,
+            "-openings" => config.openings = Some(get_single_value(option, &values)?),
+            "-pgnout" => config.pgn_file = Some(get_single_value(option, &values)?),
+            "-sprt" => {
+                let mut sprt = Sprt { elo0: 0.0, elo1: 5.0, alpha: 0.05, beta: 0.05 };
+                for (key, value) in get_key_values(&values)? {
+                    let v = value.parse::<f64>().map_err(|_| HaddockError::InvalidMatchArgs(format!("invalid sprt value {}", value)))?;
+                    match key.as_str() {
+                        "elo0" => sprt.elo0 = v,
+                        "elo1" => sprt.elo1 = v,
+                        "alpha" => sprt.alpha = v,
+                        "beta" => sprt.beta = v,
+                        _ => return Err(HaddockError::InvalidMatchArgs(format!("unknown sprt key {}", key)))
+                    }
+                }
+                config.sprt = Some(sprt);
+            }
+            _ => return Err(HaddockError::InvalidMatchArgs(format!("unknown option {}", option)))
+        }
+    }
+
+    if config.engines.len() < 2 {
+        return Err(HaddockError::InvalidMatchArgs("at least two engines are needed".to_string()));
+    }
+
+    for engine in config.engines.iter_mut() {
+        for (key, value) in each.iter() {
+            apply_engine_key(engine, key, value)?;
+        }
+    }
+
+    Ok(config)
+}
+
+fn get_key_values(values: &[&str]) -> Result<Vec<(String, String)>, HaddockError> {
+    let mut result = Vec::new();
+    for value in values {
+        let mut parts = value.splitn(2, '=');
+        match (parts.next(), parts.next()) {
+            (Some(k), Some(v)) => result.push((k.to_string(), v.to_string())),
+            _ => return Err(HaddockError::InvalidMatchArgs(format!("expected key=value, found {}", value)))
+        }
+    }
+    Ok(result)
+}
+
+fn get_single_value<T: std::str::FromStr>(option: &str, values: &[&str]) -> Result<T, HaddockError> {
+    if values.len() != 1 {
+        return Err(HaddockError::InvalidMatchArgs(format!("{} expects one value", option)));
+    }
+    values[0].parse::<T>().map_err(|_| HaddockError::InvalidMatchArgs(format!("invalid value {} for {}", values[0], option)))
+}
+
+fn apply_engine_key(engine: &mut EngineConfig, key: &str, value: &str) -> Result<(), HaddockError> {
+    let number = || value.parse::<u64>().map_err(|_| HaddockError::InvalidMatchArgs(format!("invalid value {} for {}", value, key)));
+    match key {
+        "cmd" => engine.command = value.to_string(),
+        "name" => engine.name = Some(value.to_string()),
+        "depth" => engine.search_type = SearchType::Depth(number()?),
+        "nodes" => engine.search_type = SearchType::Nodes(number()?),
+        "movetime" => engine.search_type = SearchType::MoveTime(number()?),
+        _ => {
+            if let Some(name) = key.strip_prefix("option.") {
+                engine.options.push((name.to_string(), value.to_string()));
+            } else {
+                return Err(HaddockError::InvalidMatchArgs(format!("unknown engine key {}", key)));
+            }
+        }
+    }
+    Ok(())
+}
+
+//plays the first engine against each of the others, returns the stats per opponent
+pub fn run(config: &MatchConfig) -> Result<Vec<MatchStats>, HaddockError> {
+    let openings = match &config.openings {
+        Some(file_name) => pgn::read_openings(file_name)?,
+        None => vec![Opening::startpos()]
+    };
+    if openings.is_empty() {
+        return Err(HaddockError::NoOpenings);
+    }
+
+    let mut player_a = config.engines[0].create_player()?;
+    let mut result = Vec::new();
+    let mut opponent_names = Vec::new();
+
+    for engine in config.engines.iter().skip(1) {
+        let mut player_b = engine.create_player()?;
+        opponent_names.push(player_b.get_name());
+        result.push(run_match(config, &openings, player_a.as_mut(), player_b.as_mut())?);
+    }
+
+    if result.len() > 1 {
+        println!("Gauntlet finished, Elo difference of {}", player_a.get_name());
+        for (name, stats) in opponent_names.iter().zip(result.iter()) {
+            println!("vs {}: {:.1} +/- {:.1}", name, stats.get_elo(), stats.get_elo_error());
+        }
+    }
+
+    Ok(result)
+}
+
+//games are played in pairs with swapped colours
+fn run_match(config: &MatchConfig, openings: &[Opening], player_a: &mut dyn Player, player_b: &mut dyn Player) -> Result<MatchStats, HaddockError> {
+    let name_a = player_a.get_name();
+    let name_b = player_b.get_name();
+
+    let mut stats = MatchStats::new();
+
+    for round in 0..config.games {
+        let opening = &openings[(round as usize / 2) % openings.len()];
+        let a_is_white = round % 2 == 0;
+
+        player_a.new_game()?;
+        player_b.new_game()?;
+
+        let result = if a_is_white {
+            play_game(player_a, player_b, opening, config.max_plies)?
+        } else {
+            play_game(player_b, player_a, opening, config.max_plies)?
+        };
+
+        match (result.outcome, a_is_white) {
+            (Outcome::BlackIsMate(_), true) | (Outcome::WhiteIsMate(_), false) => stats.wins += 1,
+            (Outcome::WhiteIsMate(_), true) | (Outcome::BlackIsMate(_), false) => stats.losses += 1,
+            _ => stats.draws += 1
+        }
+
+        if let Some(file_name) = &config.pgn_file {
+            let (white, black) = if a_is_white { (&name_a, &name_b) } else { (&name_b, &name_a) };
+            let pgn_game = PgnGame {
+                event: "haddock match".to_string(),
+                round: round + 1,
+                white: white.clone(),
+                black: black.clone(),
+                fen: opening.fen.clone(),
+                moves: result.moves.clone(),
+                result: result.to_pgn_result(),
+                termination: result.termination.clone()
+            };
+            append_to_file(file_name, &pgn::to_pgn(&pgn_game))?;
+        }
+
+        println!(
+            "Finished game {} ({} vs {}): {} {{{}}}",
+            round + 1,
+            if a_is_white { &name_a } else { &name_b },
+            if a_is_white { &name_b } else { &name_a },
+            result.to_pgn_result(),
+            result.termination
+        );
+        print_stats(&name_a, &name_b, &stats, &config.sprt);
+
+        if let Some(sprt) = &config.sprt {
+            if stats.get_sprt_result(sprt) != SprtResult::Continue {
+                break;
+            }
+        }
+    }
+
+    Ok(stats)
+}
+
+fn append_to_file(file_name: &str, text: &str) -> Result<(), HaddockError> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(file_name)
+        .map_err(|e| HaddockError::CannotWriteFile(file_name.to_string(), e.to_string()))?;
+    file.write_all(text.as_bytes()).map_err(|e| HaddockError::CannotWriteFile(file_name.to_string(), e.to_string()))
+}
+
+pub fn print_stats(name_a: &str, name_b: &str, stats: &MatchStats, sprt: &Option<Sprt>) {
+    println!(
+        "Score of {} vs {}: {} - {} - {}  [{:.3}] {}",
+        name_a, name_b, stats.wins, stats.losses, stats.draws, stats.get_score(), stats.get_games()
+    );
+    println!("Elo difference: {:.1} +/- {:.1}", stats.get_elo(), stats.get_elo_error());
+
+    if let Some(sprt) = sprt {
+        let (lower, upper) = sprt.get_bounds();
+        let verdict = match stats.get_sprt_result(sprt) {
+            SprtResult::AcceptH0 => " - H0 was accepted",
+            SprtResult::AcceptH1 => " - H1 was accepted",
+            SprtResult::Continue => ""
+        };
+        println!("SPRT: llr {:.2}, lbound {:.2}, ubound {:.2}{}", stats.get_llr(sprt), lower, upper, verdict);
+    }
+}
+
+//a player that cannot be reached ends the match, so the error is returned
+pub fn play_game(white: &mut dyn Player, black: &mut dyn Player, opening: &Opening, max_plies: u32) -> Result<GameResult, HaddockError> {
+    let fen_parts = opening.fen.split_whitespace().collect::<Vec<&str>>();
+    let position = parser::parse_fen(&fen_parts).expect("Error in opening FEN");
+    let mut record = GameRecord::new(position);
+
+    for &mv in opening.moves.iter() {
+        record.apply_move(mv);
+    }
+
+    loop {
+        let ply = record.get_moves().len() as i32;
+        let status = record.get_status();
+        if let Some(outcome) = status.to_outcome(ply) {
+            return Ok(GameResult { outcome, termination: status.to_string(), moves: record.get_moves().to_vec() });
+        }
+        if max_plies > 0 && ply as u32 >= max_plies {
+            return Ok(GameResult { outcome: Outcome::Draw(ply, 0), termination: "adjudication".to_string(), moves: record.get_moves().to_vec() });
+        }
+
+        let active_color = record.get_position().get_active_color();
+        let player: &mut dyn Player = if active_color == global::COLOR_WHITE { &mut *white } else { &mut *black };
+
+        let legal_move = player
+            .find_best_move(&opening.fen, record.get_moves())?
+            .and_then(|mv| get_legal_move(record.get_position(), mv));
+
+        match legal_move {
+            Some(mv) => record.apply_move(mv),
+            None => {
+                //illegal or missing move loses the game
+                let outcome = if active_color == global::COLOR_WHITE {
+                    Outcome::WhiteIsMate(ply)
+                } else {
+                    Outcome::BlackIsMate(ply)
+                };
+                return Ok(GameResult { outcome, termination: "illegal move".to_string(), moves: record.get_moves().to_vec() });
+            }
+        }
+    }
+}
+
+//the legal move matching the (possibly unanalyzed) move of a player
+fn get_legal_move(position: &Position, move_: Move_) -> Option<Move_> {
+    let fen = move_.to_fen();
+    Generator::new(position)
+        .generate_legal_moves()
+        .into_iter()
+        .find(|m| m.to_fen() == fen)
+}
@@ -1,5 +1,6 @@
 use std::fmt;
 
+use crate::error::HaddockError;
 use crate::square::Square;
 use crate::piecetype::PieceType;
 
@@ -24,12 +25,12 @@ impl Move_ {
         Move_((square_from.to_u32() << 8) | square_to.to_u32())
     }
 
-    pub fn from_str(value: &str) -> Option<Move_> {
+    pub fn from_str(value: &str) -> Result<Move_, HaddockError> {
         let mut result: u32 = 0;
         let len = value.len();
 
         if len != 4 && len != 5 {
-            return None;
+            return Err(HaddockError::InvalidMove(value.to_string()));
         }
 
         if len == 5 {
@@ -41,26 +42,26 @@ impl Move_ {
                             result |= MOVE_BIT_PROMO;
                             result |= (p.to_u8() as u32) << 24;
                         }
-                        None => return None
+                        None => return Err(HaddockError::InvalidPromotionPiece(c))
                     }
                 }
-                None => return None
+                None => return Err(HaddockError::InvalidMove(value.to_string()))
             }
         }
 
         let sq_from_str: String = value.chars().take(2).collect();
         match Square::from_str(&sq_from_str) {
-            Some::<Square>(s) => result |= (s.to_u32()) << 8,
-            None => return None
+            Ok(s) => result |= (s.to_u32()) << 8,
+            Err(_) => return Err(HaddockError::InvalidMove(value.to_string()))
         }
 
         let sq_to_str: String = value.chars().skip(2).take(2).collect();
         match Square::from_str(&sq_to_str) {
-            Some::<Square>(s) => result |= s.to_u32(),
-            None => return None
+            Ok(s) => result |= s.to_u32(),
+            Err(_) => return Err(HaddockError::InvalidMove(value.to_string()))
         }
 
-        Some(Move_(result))
+        Ok(Move_(result))
     }
 
     pub fn to_fen(self) -> String {
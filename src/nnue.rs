@@ -0,0 +1,200 @@
+use std::fs;
+
+use crate::error::HaddockError;
+use crate::global;
+use crate::piecetype::PieceType;
+use crate::position::Position;
+use crate::square::Square;
+
+//a feature per color, piece type and square, seen from one side: own pieces first, board flipped for black
+pub const INPUT_SIZE: usize = 768;
+pub const HIDDEN_SIZE: usize = 128;
+
+//quantization: hidden values are clipped to 0..QA, output weights are scaled by QB
+pub const QA: i32 = 255;
+pub const QB: i32 = 64;
+//network output to centipawns
+pub const SCALE: i32 = 400;
+//the bias and the weights of up to 32 pieces are summed in i16, within this limit they can not overflow
+pub const MAX_FEATURE_WEIGHT: i16 = (i16::MAX as i32 / 33) as i16;
+//evaluations stay clear of mate scores
+const MAX_VALUE: i64 = 10000;
+
+//"HDNN", version, input and hidden size as u32, then all weights little endian
+const FILE_MAGIC: &[u8; 4] = b"HDNN";
+const FILE_VERSION: u32 = 1;
+const HEADER_SIZE: usize = 16;
+
+//768 inputs -> 2 x HIDDEN_SIZE (side to move, other side) -> 1, the hidden layer is shared by both sides
+pub struct Network {
+    pub feature_weights: Vec<i16>, //HIDDEN_SIZE values per feature
+    pub feature_bias: Vec<i16>,
+    pub output_weights: Vec<i16>, //side to move first
+    pub output_bias: i32
+}
+
+//hidden layer values of both sides, kept per ply by the searcher
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Accumulator {
+    values: [[i16; HIDDEN_SIZE]; 2]
+}
+
+impl Default for Network {
+    fn default() -> Self {
+        Network::new()
+    }
+}
+
+impl Network {
+    //all weights zero
+    pub fn new() -> Network {
+        Network {
+            feature_weights: vec![0; INPUT_SIZE * HIDDEN_SIZE],
+            feature_bias: vec![0; HIDDEN_SIZE],
+            output_weights: vec![0; 2 * HIDDEN_SIZE],
+            output_bias: 0
+        }
+    }
+
+    pub fn load(path: &str) -> Result<Network, HaddockError> {
+        let bytes = fs::read(path).map_err(|e| HaddockError::InvalidNetworkFile(format!("{}: {}", path, e)))?;
+        Network::from_bytes(&bytes)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), HaddockError> {
+        fs::write(path, self.to_bytes()).map_err(|e| HaddockError::InvalidNetworkFile(format!("{}: {}", path, e)))
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Network, HaddockError> {
+        let expected_size = HEADER_SIZE + 2 * (INPUT_SIZE * HIDDEN_SIZE + HIDDEN_SIZE + 2 * HIDDEN_SIZE) + 4;
+        if bytes.len() < HEADER_SIZE || &bytes[0..4] != FILE_MAGIC {
+            return Err(HaddockError::InvalidNetworkFile("not a network file".to_string()));
+        }
+
+        let version = read_u32(bytes, 4);
+        let input_size = read_u32(bytes, 8) as usize;
+        let hidden_size = read_u32(bytes, 12) as usize;
+        if version != FILE_VERSION {
+            return Err(HaddockError::InvalidNetworkFile(format!("version {}, {} expected", version, FILE_VERSION)));
+        }
+        if input_size != INPUT_SIZE || hidden_size != HIDDEN_SIZE {
+            return Err(HaddockError::InvalidNetworkFile(format!("{}x{} network, {}x{} expected", input_size, hidden_size, INPUT_SIZE, HIDDEN_SIZE)));
+        }
+        if bytes.len() != expected_size {
+            return Err(HaddockError::InvalidNetworkFile(format!("{} bytes, {} expected", bytes.len(), expected_size)));
+        }
+
+        let mut network = Network::new();
+        let mut offset = HEADER_SIZE;
+        for values in [&mut network.feature_weights, &mut network.feature_bias, &mut network.output_weights] {
+            for v in values.iter_mut() {
+                *v = i16::from_le_bytes([bytes[offset], bytes[offset + 1]]);
+                offset += 2;
+            }
+        }
+        network.output_bias = read_u32(bytes, offset) as i32;
+
+        if network.feature_weights.iter().chain(network.feature_bias.iter()).any(|w| (*w as i32).abs() > MAX_FEATURE_WEIGHT as i32) {
+            return Err(HaddockError::InvalidNetworkFile(format!("feature weights above {}", MAX_FEATURE_WEIGHT)));
+        }
+        Ok(network)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(FILE_MAGIC);
+        for n in [FILE_VERSION, INPUT_SIZE as u32, HIDDEN_SIZE as u32] {
+            bytes.extend_from_slice(&n.to_le_bytes());
+        }
+        for values in [&self.feature_weights, &self.feature_bias, &self.output_weights] {
+            for v in values.iter() {
+                bytes.extend_from_slice(&v.to_le_bytes());
+            }
+        }
+        bytes.extend_from_slice(&self.output_bias.to_le_bytes());
+        bytes
+    }
+
+    //accumulator of a position from scratch
+    pub fn get_accumulator(&self, position: &Position) -> Accumulator {
+        let mut accumulator = Accumulator {
+            values: [[0; HIDDEN_SIZE]; 2]
+        };
+        for side in 0..2 {
+            accumulator.values[side].copy_from_slice(&self.feature_bias);
+        }
+        for (piece, square) in position.get_all_active_pieces() {
+            self.add_piece(&mut accumulator, square, piece);
+        }
+        accumulator
+    }
+
+    //accumulator of a position reached with a move from the position of accumulator,
+    //only the pieces that differ between both boards are updated
+    pub fn update_accumulator(&self, accumulator: &mut Accumulator, from: &Position, to: &Position) {
+        for piece_value in 0..12 {
+            let piece = PieceType::new(piece_value);
+            let from_board = from.get_bit_board(piece);
+            let to_board = to.get_bit_board(piece);
+            if from_board == to_board {
+                continue;
+            }
+            for square in (from_board & !to_board).get_squares() {
+                self.remove_piece(accumulator, square, piece);
+            }
+            for square in (to_board & !from_board).get_squares() {
+                self.add_piece(accumulator, square, piece);
+            }
+        }
+    }
+
+    fn add_piece(&self, accumulator: &mut Accumulator, square: Square, piece: PieceType) {
+        for side in [global::COLOR_WHITE, global::COLOR_BLACK] {
+            let start = get_feature_index(side, piece, square) * HIDDEN_SIZE;
+            let weights = &self.feature_weights[start..start + HIDDEN_SIZE];
+            for (v, w) in accumulator.values[side as usize].iter_mut().zip(weights) {
+                *v += *w;
+            }
+        }
+    }
+
+    fn remove_piece(&self, accumulator: &mut Accumulator, square: Square, piece: PieceType) {
+        for side in [global::COLOR_WHITE, global::COLOR_BLACK] {
+            let start = get_feature_index(side, piece, square) * HIDDEN_SIZE;
+            let weights = &self.feature_weights[start..start + HIDDEN_SIZE];
+            for (v, w) in accumulator.values[side as usize].iter_mut().zip(weights) {
+                *v -= *w;
+            }
+        }
+    }
+
+    //centipawns from the side of color
+    //summed in i64, with large output weights the sum does not fit in i32
+    pub fn evaluate(&self, accumulator: &Accumulator, color: u8) -> i32 {
+        let own = &accumulator.values[color as usize];
+        let other = &accumulator.values[1 - color as usize];
+        let (own_weights, other_weights) = self.output_weights.split_at(HIDDEN_SIZE);
+
+        let mut sum: i64 = self.output_bias as i64;
+        for i in 0..HIDDEN_SIZE {
+            sum += clipped_relu(own[i]) as i64 * own_weights[i] as i64;
+            sum += clipped_relu(other[i]) as i64 * other_weights[i] as i64;
+        }
+        (sum * SCALE as i64 / (QA * QB) as i64).clamp(-MAX_VALUE, MAX_VALUE) as i32
+    }
+}
+
+//input index of a piece as seen by side
+pub fn get_feature_index(side: u8, piece: PieceType, square: Square) -> usize {
+    let relative_color = (piece.get_color() != side) as usize;
+    let relative_square = if side == global::COLOR_WHITE { square.to_usize() } else { square.to_usize() ^ 56 };
+    (relative_color * 6 + (piece.to_usize() >> 1)) * 64 + relative_square
+}
+
+fn clipped_relu(value: i16) -> i32 {
+    (value as i32).clamp(0, QA)
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([bytes[offset], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]])
+}
@@ -10,7 +10,7 @@ pub enum Outcome {
     Undecided(i32, i32),
     WhiteIsMate(i32),
     BlackIsMate(i32),
-    Draw(i32)}
+    Draw(i32, i32)} //depth, value from white's side, not 0 with contempt
 
 impl Outcome {
     pub fn score(&self) -> i32 {
@@ -18,7 +18,7 @@ impl Outcome {
             Outcome::Undecided(_, material_value) => *material_value,
             Outcome::WhiteIsMate(d) => MATE_AGAINST_WHITE + d, // to look for quicker mate
             Outcome::BlackIsMate(d) => MATE_AGAINST_BLACK - d,
-            _ => 0
+            Outcome::Draw(_, value) => *value
         }
     }
 
@@ -32,7 +32,7 @@ impl Outcome {
             Outcome::Undecided(_, material_value) => format!("cp {}", mult * *material_value),
             Outcome::BlackIsMate(depth) => format!("mate {}", mult * (*depth + 1) / 2), //plies to moves
             Outcome::WhiteIsMate(depth) => format!("mate {}", - mult * (*depth + 1) / 2), //plies to moves
-            _ => "cp 0".to_string()
+            Outcome::Draw(_, value) => format!("cp {}", mult * *value)
         }
     }
 
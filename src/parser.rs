@@ -1,48 +1,35 @@
+use crate::error::HaddockError;
 use crate::global;
 use crate::position::Position;
 use crate::piecetype::PieceType;
 use crate::square::Square;
 
-pub fn parse_startpos() -> Option<Position> {
+pub fn parse_startpos() -> Result<Position, HaddockError> {
     let fen_parts = global::FEN_STARTPOS.split(" ").collect::<Vec<&str>>();
     parse_fen(&fen_parts)
 }
 
-pub fn parse_fen(fen_parts: &[&str]) -> Option<Position> {
+pub fn parse_fen(fen_parts: &[&str]) -> Result<Position, HaddockError> {
     if fen_parts.len() < 2 {
-        return None;
+        return Err(HaddockError::FenTooShort);
+    }
+    if fen_parts.len() > 6 {
+        return Err(HaddockError::FenTooManyFields(fen_parts.len()));
     }
 
     let adj_fen_parts = apply_fen_defaults(fen_parts);
 
     let mut position = Position::new();
 
-    if !parse_fen_pieces(&mut position, &adj_fen_parts[0]) {
-        return None;
-    }
-
-    if !parse_fen_color(&mut position, &adj_fen_parts[1]) {
-        return None;
-    }
-
-    if !parse_fen_castling(&mut position, &adj_fen_parts[2]) {
-        return None;
-    }
-
-    if !parse_fen_enpassant(&mut position, &adj_fen_parts[3]) {
-        return None;
-    }
-
-    if !parse_fen_halfmoveclock(&mut position, &adj_fen_parts[4]) {
-        return None;
-    }
-
-    if !parse_fen_fullmovenumber(&mut position, &adj_fen_parts[5]) {
-        return None;
-    }
+    parse_fen_pieces(&mut position, &adj_fen_parts[0])?;
+    parse_fen_color(&mut position, &adj_fen_parts[1])?;
+    parse_fen_castling(&mut position, &adj_fen_parts[2])?;
+    parse_fen_enpassant(&mut position, &adj_fen_parts[3])?;
+    parse_fen_halfmoveclock(&mut position, &adj_fen_parts[4])?;
+    parse_fen_fullmovenumber(&mut position, &adj_fen_parts[5])?;
 
     position.generate_new_hash();
-    Some(position)
+    Ok(position)
 }
 
 fn apply_fen_defaults(fen_parts: &[&str]) -> [String; 6] {
@@ -88,63 +75,64 @@ fn apply_fen_defaults(fen_parts: &[&str]) -> [String; 6] {
     result
 }
 
-fn parse_fen_pieces(position: &mut Position, fen_pieces: &str) -> bool {
+fn parse_fen_pieces(position: &mut Position, fen_pieces: &str) -> Result<(), HaddockError> {
     let rows = fen_pieces.split("/").collect::<Vec<&str>>();
     if rows.len() != 8 {
-        return false;
+        return Err(HaddockError::BadRankCount(rows.len()));
     }
 
     for i in 0u32..8 {
         let y = 7 - i;
         let mut x: u32 = 0;
         for c in rows[i as usize].chars() {
-            if !c.is_ascii() {
-                continue;
-            }
-            if x > 7 {
-                return false;
-            }
             match c.to_digit(10) {
-                Some(d) => {
+                Some(d) if d > 0 => {
                     x += d;
                     continue;
                 },
-                None => ()
+                _ => ()
             }
             match PieceType::from_char(c) {
                 Some(piece_type) => {
-                    position.set_piece(Square::from_xy(x as u8, y as u8), piece_type);
+                    if x <= 7 {
+                        position.set_piece(Square::from_xy(x as u8, y as u8), piece_type);
+                    }
                     x += 1;
                 },
-                None => return false
+                None => return Err(HaddockError::InvalidPieceChar(c))
             }
         }
+        if x != 8 {
+            return Err(HaddockError::BadRankLength((y + 1) as u8, x));
+        }
     }
 
-    true
+    Ok(())
 }
 
-fn parse_fen_color(position: &mut Position, fen_color: &str) -> bool {
-    if fen_color.len() != 1 {
-        return false;
-    }
-
+fn parse_fen_color(position: &mut Position, fen_color: &str) -> Result<(), HaddockError> {
     let lc = fen_color.to_ascii_lowercase();
     if lc == "w" {
         position.set_active_color(global::COLOR_WHITE);
-        return true;
+        return Ok(());
     }
     else if lc == "b" {
         position.set_active_color(global::COLOR_BLACK);
-        return true;
+        return Ok(());
     }
 
-    return false;
+    Err(HaddockError::InvalidActiveColor(fen_color.to_string()))
 }
 
-fn parse_fen_castling(position: &mut Position, fen_castling: &str) -> bool {
-    if fen_castling.len() > 4 {
-        return false;
+fn parse_fen_castling(position: &mut Position, fen_castling: &str) -> Result<(), HaddockError> {
+    if fen_castling != "-" {
+        //each of KQkq at most once, nothing else
+        let valid = !fen_castling.is_empty() && fen_castling.chars().enumerate().all(|(i, c)| {
+            "KQkq".contains(c) && !fen_castling[..i].contains(c)
+        });
+        if !valid {
+            return Err(HaddockError::IllegalCastlingField(fen_castling.to_string()));
+        }
     }
 
     position.set_castling_status(0, fen_castling.contains('K'));
@@ -152,38 +140,36 @@ fn parse_fen_castling(position: &mut Position, fen_castling: &str) -> bool {
     position.set_castling_status(2, fen_castling.contains('k'));
     position.set_castling_status(3, fen_castling.contains('q'));
 
-    true
+    Ok(())
 }
 
-fn parse_fen_enpassant(position: &mut Position, fen_enpassant: &str) -> bool {
+fn parse_fen_enpassant(position: &mut Position, fen_enpassant: &str) -> Result<(), HaddockError> {
     if fen_enpassant == "-" {
-        return true;
+        return Ok(());
     }
 
-    let square = Square::from_str(fen_enpassant);
-
-    match square {
-        Some(found_square) => position.set_enpassant_square(Some(found_square)),
-        None => return false
+    match Square::from_str(fen_enpassant) {
+        Ok(found_square) => position.set_enpassant_square(Some(found_square)),
+        Err(_) => return Err(HaddockError::InvalidEnPassantSquare(fen_enpassant.to_string()))
     }
 
-    true
+    Ok(())
 }
 
-fn parse_fen_halfmoveclock(position: &mut Position, fen_halfmoveclock: &str) -> bool {
+fn parse_fen_halfmoveclock(position: &mut Position, fen_halfmoveclock: &str) -> Result<(), HaddockError> {
     match fen_halfmoveclock.parse::<u32>() {
         Ok(n) => position.set_halfmoveclock(n),
-        Err(_) => return false
+        Err(_) => return Err(HaddockError::InvalidHalfMoveClock(fen_halfmoveclock.to_string()))
     }
-    true
+    Ok(())
 }
 
-fn parse_fen_fullmovenumber(position: &mut Position, fen_fullmovenumber: &str) -> bool {
+fn parse_fen_fullmovenumber(position: &mut Position, fen_fullmovenumber: &str) -> Result<(), HaddockError> {
     match fen_fullmovenumber.parse::<u32>() {
         Ok(n) => position.set_fullmovenumber(n),
-        Err(_) => return false
+        Err(_) => return Err(HaddockError::InvalidFullMoveNumber(fen_fullmovenumber.to_string()))
     }
-    true
+    Ok(())
 }
 
 #[allow(dead_code)] //for testing
@@ -0,0 +1,42 @@
+use crate::pawnstructure::PawnEntry;
+
+pub const DEFAULT_SIZE: usize = 16384;
+
+#[derive(Copy, Clone)]
+struct PawnHashTableEntry {
+    pawn_hash: u64,
+    entry: PawnEntry
+}
+
+//cache of pawn structure evaluations, the slot is selected by the lowest bits of the pawn hash
+pub struct PawnHashTable {
+    entries: Vec<Option<PawnHashTableEntry>>,
+    mask: u64
+}
+
+impl PawnHashTable {
+    //size must be a power of two
+    pub fn new(size: usize) -> Self {
+        PawnHashTable {
+            entries: vec![None; size],
+            mask: (size - 1) as u64
+        }
+    }
+
+    pub fn clear(&mut self) {
+        for e in self.entries.iter_mut() {
+            *e = None;
+        }
+    }
+
+    pub fn insert(&mut self, pawn_hash: u64, entry: PawnEntry) {
+        self.entries[(pawn_hash & self.mask) as usize] = Some(PawnHashTableEntry { pawn_hash, entry });
+    }
+
+    pub fn get(&self, pawn_hash: u64) -> Option<PawnEntry> {
+        match &self.entries[(pawn_hash & self.mask) as usize] {
+            Some(e) if e.pawn_hash == pawn_hash => Some(e.entry),
+            _ => None
+        }
+    }
+}
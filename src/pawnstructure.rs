@@ -0,0 +1,132 @@
+use crate::bitboard::BitBoard;
+use crate::evalparams::EvalParams;
+use crate::global::{COLOR_WHITE, COLOR_BLACK};
+use crate::piecetype::PieceType;
+use crate::position::Position;
+use crate::score::Score;
+
+//structure of both colors, only depends on pawns so it can be cached by pawn hash
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PawnEntry {
+    pub score: Score, //from white's side
+    pub passed: [BitBoard; 2]
+}
+
+pub fn evaluate_pawns(position: &Position, params: &EvalParams) -> PawnEntry {
+    let pawns = [
+        position.get_bit_board(PieceType::new_pawn(COLOR_WHITE)).to_u64(),
+        position.get_bit_board(PieceType::new_pawn(COLOR_BLACK)).to_u64()
+    ];
+
+    let (white_score, white_passed) = evaluate_color_pawns(COLOR_WHITE, pawns[0], pawns[1], params);
+    let (black_score, black_passed) = evaluate_color_pawns(COLOR_BLACK, pawns[1], pawns[0], params);
+
+    PawnEntry {
+        score: white_score - black_score,
+        passed: [BitBoard::from_u64(white_passed), BitBoard::from_u64(black_passed)]
+    }
+}
+
+//the free path depends on all pieces, so it is not part of the cached entry
+pub fn evaluate_passed_pawns(position: &Position, entry: &PawnEntry, params: &EvalParams) -> Score {
+    evaluate_passed_pawns_color(position, entry, COLOR_WHITE, params) - evaluate_passed_pawns_color(position, entry, COLOR_BLACK, params)
+}
+
+//free path bonus from the side of the given color
+pub fn evaluate_passed_pawns_color(position: &Position, entry: &PawnEntry, color: u8, params: &EvalParams) -> Score {
+    let occupied = (position.get_piece_board(COLOR_WHITE) | position.get_piece_board(COLOR_BLACK)).to_u64();
+    let mut score = Score::default();
+    for square in entry.passed[color as usize].get_squares() {
+        let (x, y) = square.to_xy();
+        if file(x) & ranks_ahead(color, y) & occupied == 0 {
+            score += params.passed_pawn_free_path[relative_rank(color, y)];
+        }
+    }
+    score
+}
+
+//structure score from the side of the given color, uncached
+pub fn evaluate_color(position: &Position, color: u8, params: &EvalParams) -> Score {
+    let own = position.get_bit_board(PieceType::new_pawn(color)).to_u64();
+    let enemy = position.get_bit_board(PieceType::new_pawn(1 - color)).to_u64();
+    evaluate_color_pawns(color, own, enemy, params).0
+}
+
+//score from the side of the given color, and its passed pawns
+fn evaluate_color_pawns(color: u8, own: u64, enemy: u64, params: &EvalParams) -> (Score, u64) {
+    let mut score = Score::default();
+    let mut passed = 0;
+
+    for square in BitBoard::from_u64(own).get_squares() {
+        let (x, y) = square.to_xy();
+        let rank = relative_rank(color, y);
+        let ahead = ranks_ahead(color, y);
+        let adjacent = adjacent_files(x);
+
+        //the rear pawn of a file is the doubled one
+        if own & file(x) & ahead != 0 {
+            score += params.doubled_pawn;
+        }
+
+        if own & adjacent == 0 {
+            score += params.isolated_pawn;
+        } else {
+            let supported = own & adjacent & rank_board(forward_y(color, y, -1)) != 0;
+            let phalanx = own & adjacent & rank_board(y as i32) != 0;
+            if supported || phalanx {
+                score += params.connected_pawn[rank];
+            }
+
+            //no pawn can support it and the square in front is controlled by an enemy pawn
+            let behind_or_level = !ahead;
+            let stop_attacked = enemy & adjacent & rank_board(forward_y(color, y, 2)) != 0;
+            if own & adjacent & behind_or_level == 0 && stop_attacked {
+                score += params.backward_pawn;
+            }
+        }
+
+        if enemy & (file(x) | adjacent) & ahead == 0 {
+            score += params.passed_pawn[rank];
+            passed |= 1u64 << square.to_usize();
+        }
+    }
+
+    (score, passed)
+}
+
+pub fn relative_rank(color: u8, y: u8) -> usize {
+    if color == COLOR_WHITE { y as usize } else { 7 - y as usize }
+}
+
+//rank index a number of ranks forward for the color, can be off the board
+fn forward_y(color: u8, y: u8, ranks: i32) -> i32 {
+    if color == COLOR_WHITE { y as i32 + ranks } else { y as i32 - ranks }
+}
+
+fn file(x: u8) -> u64 {
+    BitBoard::from_file(x).to_u64()
+}
+
+pub fn adjacent_files(x: u8) -> u64 {
+    let mut result = 0;
+    if x > 0 {
+        result |= file(x - 1);
+    }
+    if x < 7 {
+        result |= file(x + 1);
+    }
+    result
+}
+
+fn rank_board(y: i32) -> u64 {
+    if (0..8).contains(&y) { BitBoard::from_rank(y as u8).to_u64() } else { 0 }
+}
+
+//all squares on the ranks in front of y, seen from the color
+pub fn ranks_ahead(color: u8, y: u8) -> u64 {
+    if color == COLOR_WHITE {
+        if y >= 7 { 0 } else { !0u64 << (8 * (y + 1)) }
+    } else {
+        (1u64 << (8 * y)) - 1
+    }
+}
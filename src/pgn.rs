@@ -0,0 +1,219 @@
+use std::fs;
+
+use crate::error::HaddockError;
+use crate::global;
+use crate::move_::Move_;
+use crate::parser;
+use crate::validation;
+use crate::position::Position;
+use crate::san;
+
+const PGN_LINE_LENGTH: usize = 80;
+
+//a start position plus the moves played from it
+#[derive(Clone)]
+pub struct Opening {
+    pub fen: String,
+    pub moves: Vec<Move_>
+}
+
+impl Opening {
+    pub fn startpos() -> Opening {
+        Opening { fen: global::FEN_STARTPOS.to_string(), moves: Vec::new() }
+    }
+}
+
+pub struct PgnGame {
+    pub event: String,
+    pub round: u32,
+    pub white: String,
+    pub black: String,
+    pub fen: String,
+    pub moves: Vec<Move_>,
+    pub result: String,
+    pub termination: String
+}
+
+//reads openings from an EPD file (one position per line) or from a PGN file (extension .pgn)
+pub fn read_openings(file_name: &str) -> Result<Vec<Opening>, HaddockError> {
+    let is_pgn = file_name.to_lowercase().ends_with(".pgn");
+    let content = match fs::read_to_string(file_name) {
+        Ok(c) => c,
+        Err(e) if is_pgn => return Err(HaddockError::InvalidPgn(format!("{}: {}", file_name, e))),
+        Err(e) => return Err(HaddockError::InvalidEpd(format!("{}: {}", file_name, e)))
+    };
+
+    if is_pgn {
+        parse_pgn_openings(&content)
+    } else {
+        parse_epd_openings(&content)
+    }
+}
+
+pub fn parse_epd_openings(content: &str) -> Result<Vec<Opening>, HaddockError> {
+    let mut result = Vec::new();
+
+    for (i, line) in content.lines().enumerate() {
+        let fen_parts = line.split_whitespace().take(4).collect::<Vec<&str>>();
+        if fen_parts.is_empty() || fen_parts[0].starts_with('#') {
+            continue;
+        }
+        if fen_parts.len() < 4 {
+            return Err(HaddockError::InvalidEpd(format!("line {}: incomplete position", i + 1)));
+        }
+        match parser::parse_fen(&fen_parts).and_then(|p| validation::validate_position(&p).map(|_| p)) {
+            Ok(position) => result.push(Opening { fen: parser::get_position_fen(&position), moves: Vec::new() }),
+            Err(e) => return Err(HaddockError::InvalidEpd(format!("line {}: {}", i + 1, e)))
+        }
+    }
+
+    Ok(result)
+}
+
+pub fn parse_pgn_openings(content: &str) -> Result<Vec<Opening>, HaddockError> {
+    let mut result = Vec::new();
+    let mut fen = global::FEN_STARTPOS.to_string();
+    let mut movetext = "".to_string();
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            //a tag after movetext starts a new game
+            if !movetext.trim().is_empty() {
+                result.push(parse_pgn_game(&fen, &movetext)?);
+                fen = global::FEN_STARTPOS.to_string();
+                movetext.clear();
+            }
+            if let Some((key, value)) = parse_tag(line) {
+                if key == "FEN" {
+                    fen = value;
+                }
+            }
+        } else {
+            //rest of line comment
+            let line = match line.find(';') {
+                Some(i) => &line[..i],
+                None => line
+            };
+            movetext.push_str(line);
+            movetext.push(' ');
+        }
+    }
+
+    if !movetext.trim().is_empty() {
+        result.push(parse_pgn_game(&fen, &movetext)?);
+    }
+
+    Ok(result)
+}
+
+fn parse_tag(line: &str) -> Option<(String, String)> {
+    let inner = line.trim_start_matches('[').trim_end_matches(']');
+    let mut parts = inner.splitn(2, ' ');
+    let key = parts.next()?.to_string();
+    let value = parts.next()?.trim().trim_matches('"').to_string();
+    Some((key, value))
+}
+
+fn parse_pgn_game(fen: &str, movetext: &str) -> Result<Opening, HaddockError> {
+    let fen_parts = fen.split_whitespace().collect::<Vec<&str>>();
+    let mut position = match parser::parse_fen(&fen_parts).and_then(|p| validation::validate_position(&p).map(|_| p)) {
+        Ok(p) => p,
+        Err(e) => return Err(HaddockError::InvalidPgn(format!("FEN tag {}, {}", fen, e)))
+    };
+
+    let mut moves = Vec::new();
+    for token in get_movetext_tokens(movetext) {
+        match san::parse_san(&position, &token) {
+            Some(mv) => {
+                position.apply_move(mv);
+                moves.push(mv);
+            }
+            None => return Err(HaddockError::InvalidPgn(format!("move {}", token)))
+        }
+    }
+
+    Ok(Opening { fen: fen.to_string(), moves })
+}
+
+//strips comments, variations, move numbers, NAGs and results from movetext
+fn get_movetext_tokens(movetext: &str) -> Vec<String> {
+    let mut clean = "".to_string();
+    let mut comment = false;
+    let mut variation_level = 0;
+
+    for c in movetext.chars() {
+        match c {
+            '{' => comment = true,
+            '}' => comment = false,
+            '(' if !comment => variation_level += 1,
+            ')' if !comment => variation_level -= 1,
+            _ if !comment && variation_level == 0 => clean.push(c),
+            _ => ()
+        }
+    }
+
+    let mut result = Vec::new();
+    for word in clean.split_whitespace() {
+        if word.starts_with('$') || ["1-0", "0-1", "1/2-1/2", "*"].contains(&word) {
+            continue;
+        }
+        let word = word.trim_start_matches(|c: char| c.is_ascii_digit() || c == '.');
+        if !word.is_empty() {
+            result.push(word.to_string());
+        }
+    }
+    result
+}
+
+pub fn to_pgn(game: &PgnGame) -> String {
+    let mut pgn = "".to_string();
+
+    pgn.push_str(&format!("[Event \"{}\"]\n", game.event));
+    pgn.push_str("[Site \"?\"]\n");
+    pgn.push_str("[Date \"????.??.??\"]\n");
+    pgn.push_str(&format!("[Round \"{}\"]\n", game.round));
+    pgn.push_str(&format!("[White \"{}\"]\n", game.white));
+    pgn.push_str(&format!("[Black \"{}\"]\n", game.black));
+    pgn.push_str(&format!("[Result \"{}\"]\n", game.result));
+    if game.fen != global::FEN_STARTPOS {
+        pgn.push_str("[SetUp \"1\"]\n");
+        pgn.push_str(&format!("[FEN \"{}\"]\n", game.fen));
+    }
+    pgn.push_str(&format!("[PlyCount \"{}\"]\n", game.moves.len()));
+    pgn.push_str(&format!("[Termination \"{}\"]\n", game.termination));
+    pgn.push('\n');
+
+    let fen_parts = game.fen.split_whitespace().collect::<Vec<&str>>();
+    let mut position: Position = parser::parse_fen(&fen_parts).expect("Error in PGN FEN");
+
+    let mut tokens: Vec<String> = Vec::new();
+    for (i, &mv) in game.moves.iter().enumerate() {
+        let number = position.get_fullmovenumber();
+        if position.get_active_color() == global::COLOR_WHITE {
+            tokens.push(format!("{}.", number));
+        } else if i == 0 {
+            tokens.push(format!("{}...", number));
+        }
+        tokens.push(san::move_to_san(&position, mv));
+        position.apply_move(mv);
+    }
+    tokens.push(game.result.clone());
+
+    let mut line = "".to_string();
+    for token in tokens {
+        if !line.is_empty() && line.len() + token.len() + 1 > PGN_LINE_LENGTH {
+            pgn.push_str(&line);
+            pgn.push('\n');
+            line.clear();
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(&token);
+    }
+    pgn.push_str(&line);
+    pgn.push_str("\n\n");
+
+    pgn
+}
@@ -0,0 +1,123 @@
+use crate::bitboard::BitBoard;
+use crate::evalparams::EvalParams;
+use crate::generator::Generator;
+use crate::global::{COLOR_WHITE, COLOR_BLACK};
+use crate::pawnstructure;
+use crate::piecetype::PieceType;
+use crate::position::Position;
+use crate::score::Score;
+use crate::square::Square;
+
+//mobility and piece placement, from white's side
+pub fn evaluate_pieces(position: &Position, params: &EvalParams) -> Score {
+    let generator = Generator::new(position);
+    evaluate_color(position, &generator, COLOR_WHITE, params) - evaluate_color(position, &generator, COLOR_BLACK, params)
+}
+
+//score from the side of the given color
+pub fn evaluate_color(position: &Position, generator: &Generator, color: u8, params: &EvalParams) -> Score {
+    let other_color = 1 - color;
+    let own_pawns = position.get_bit_board(PieceType::new_pawn(color));
+    let enemy_pawns = position.get_bit_board(PieceType::new_pawn(other_color));
+
+    //squares controlled by enemy pawns are not counted for mobility
+    let mut enemy_pawn_attacks = BitBoard::new();
+    for square in enemy_pawns.get_squares() {
+        enemy_pawn_attacks |= generator.get_attack_board(square, PieceType::new_pawn(other_color));
+    }
+    let mobility_area = !(position.get_piece_board(color) | enemy_pawn_attacks);
+
+    let mut score = Score::default();
+
+    let knight = PieceType::new_knight(color);
+    for square in position.get_bit_board(knight).get_squares() {
+        let mobility = (generator.get_attack_board(square, knight) & mobility_area).get_count() as i32;
+        score += params.knight_mobility * (mobility - params.knight_mobility_base);
+        if is_outpost(square, color, own_pawns, enemy_pawns, generator) {
+            score += params.knight_outpost;
+        }
+    }
+
+    let bishop = PieceType::new_bishop(color);
+    for square in position.get_bit_board(bishop).get_squares() {
+        let mobility = (generator.get_attack_board(square, bishop) & mobility_area).get_count() as i32;
+        score += params.bishop_mobility * (mobility - params.bishop_mobility_base);
+        if is_trapped_bishop(square, color, enemy_pawns) {
+            score += params.trapped_bishop;
+        }
+    }
+    if position.get_piece_count(bishop) >= 2 {
+        score += params.bishop_pair;
+    }
+
+    let rook = PieceType::new_rook(color);
+    for square in position.get_bit_board(rook).get_squares() {
+        let mobility = (generator.get_attack_board(square, rook) & mobility_area).get_count() as i32;
+        score += params.rook_mobility * (mobility - params.rook_mobility_base);
+
+        let (x, y) = square.to_xy();
+        let file = BitBoard::from_file(x);
+        if (own_pawns & file).get_count() == 0 {
+            score += if (enemy_pawns & file).get_count() == 0 { params.rook_open_file } else { params.rook_semi_open_file };
+        }
+
+        //seventh rank counts when it cuts off the king or attacks pawns
+        if pawnstructure::relative_rank(color, y) == 6 {
+            let (_, king_y) = position.get_king_square(other_color).to_xy();
+            if pawnstructure::relative_rank(color, king_y) == 7 || (enemy_pawns & BitBoard::from_rank(y)).not_empty() {
+                score += params.rook_on_seventh;
+            }
+        }
+
+        if mobility <= params.trapped_rook_max_mobility && is_rook_behind_king(position, square, color) {
+            score += params.trapped_rook;
+        }
+    }
+
+    let queen = PieceType::new_queen(color);
+    for square in position.get_bit_board(queen).get_squares() {
+        let mobility = (generator.get_attack_board(square, queen) & mobility_area).get_count() as i32;
+        score += params.queen_mobility * (mobility - params.queen_mobility_base);
+    }
+
+    score
+}
+
+//supported by an own pawn in enemy territory, and no enemy pawn can ever attack it
+fn is_outpost(square: Square, color: u8, own_pawns: BitBoard, enemy_pawns: BitBoard, generator: &Generator) -> bool {
+    let (x, y) = square.to_xy();
+    let rank = pawnstructure::relative_rank(color, y);
+    if !(3..=5).contains(&rank) {
+        return false;
+    }
+
+    //own pawns defending the square are on the squares an enemy pawn there would attack
+    let defenders = generator.get_attack_board(square, PieceType::new_pawn(1 - color)) & own_pawns;
+    let attackers = pawnstructure::adjacent_files(x) & pawnstructure::ranks_ahead(color, y) & enemy_pawns.to_u64();
+    defenders.not_empty() && attackers == 0
+}
+
+fn is_trapped_bishop(square: Square, color: u8, enemy_pawns: BitBoard) -> bool {
+    let (x, y) = square.to_xy();
+    if pawnstructure::relative_rank(color, y) != 6 || (x != 0 && x != 7) {
+        return false;
+    }
+
+    //the pawn diagonally in front on the inner side
+    let pawn_x = if x == 0 { 1 } else { 6 };
+    let pawn_y = if color == COLOR_WHITE { y - 1 } else { y + 1 };
+    enemy_pawns.check(Square::from_xy(pawn_x, pawn_y))
+}
+
+//rook on the back rank on the corner side of its king, that can no longer castle
+fn is_rook_behind_king(position: &Position, square: Square, color: u8) -> bool {
+    let (rook_x, rook_y) = square.to_xy();
+    let (king_x, king_y) = position.get_king_square(color).to_xy();
+    if pawnstructure::relative_rank(color, rook_y) != 0 || king_y != rook_y {
+        return false;
+    }
+
+    let castling_index = if color == COLOR_WHITE { 0 } else { 2 };
+    let can_castle = position.get_castling_status(castling_index) || position.get_castling_status(castling_index + 1);
+    !can_castle && ((king_x >= 4 && rook_x > king_x) || (king_x <= 3 && rook_x < king_x))
+}
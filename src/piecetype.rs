@@ -92,6 +92,10 @@ impl PieceType {
         self.0 & MASK_TYPE
     }
 
+    pub fn get_color(self) -> u8 {
+        self.0 & !MASK_TYPE
+    }
+
     pub fn is_pawn(self) -> bool {
          self.0 & MASK_TYPE == PIECE_PAWN
     }
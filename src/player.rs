@@ -0,0 +1,136 @@
+use std::sync::atomic::AtomicBool;
+use std::sync::mpsc;
+use std::sync::Arc;
+
+use crate::command;
+use crate::error::HaddockError;
+use crate::evalparams::EvalParams;
+use crate::gamerecord::GameRecord;
+use crate::hash_counter::HashCounter;
+use crate::move_::Move_;
+use crate::nnue::Network;
+use crate::parser;
+use crate::searcher::Searcher;
+use crate::searchtype::SearchType;
+use crate::uciclient::UciClient;
+
+//something that can play moves in an engine match
+//errors are failures to talk to the engine, no move means the engine gave up
+pub trait Player {
+    fn get_name(&self) -> String;
+    fn new_game(&mut self) -> Result<(), HaddockError>;
+    fn find_best_move(&mut self, fen: &str, moves: &[Move_]) -> Result<Option<Move_>, HaddockError>;
+}
+
+//plays with the built-in searcher, its tables are kept during a game
+pub struct SearcherPlayer {
+    name: String,
+    search_type: SearchType,
+    searcher: Searcher,
+    use_nnue: bool,
+    network: Option<Arc<Network>>
+}
+
+impl SearcherPlayer {
+    pub fn new(name: &str, search_type: SearchType) -> SearcherPlayer {
+        //the receiver is never read, the search is run synchronously
+        let (_sender, receiver) = mpsc::channel();
+        let position = parser::parse_startpos().expect("Error in startpos FEN");
+        let searcher = Searcher::new(receiver, position, Arc::new(AtomicBool::new(false)), HashCounter::new());
+        SearcherPlayer { name: name.to_string(), search_type, searcher, use_nnue: false, network: None }
+    }
+
+    //the uci options of the engine that matter in a match, others are rejected
+    pub fn with_options(name: &str, search_type: SearchType, options: &[(String, String)]) -> Result<SearcherPlayer, HaddockError> {
+        let mut player = SearcherPlayer::new(name, search_type);
+        for (name, value) in options {
+            player.set_option(name, value)?;
+        }
+        if player.use_nnue && player.network.is_none() {
+            return Err(HaddockError::MissingEngineOption(command::OPTION_USE_NNUE.to_string(), command::OPTION_NNUE_FILE.to_string()));
+        }
+        Ok(player)
+    }
+
+    pub fn set_option(&mut self, name: &str, value: &str) -> Result<(), HaddockError> {
+        if name.eq_ignore_ascii_case(command::OPTION_CONTEMPT) {
+            let contempt = value.parse::<i32>().map_err(|_| HaddockError::InvalidEngineOptionValue(name.to_string(), value.to_string()))?;
+            self.searcher.set_contempt(contempt.clamp(command::MIN_CONTEMPT, command::MAX_CONTEMPT));
+        } else if name.eq_ignore_ascii_case(command::OPTION_EVAL_FILE) {
+            self.searcher.set_eval_params(EvalParams::load(value)?);
+        } else if name.eq_ignore_ascii_case(command::OPTION_USE_NNUE) {
+            self.use_nnue = value.eq_ignore_ascii_case("true");
+            self.send_network();
+        } else if name.eq_ignore_ascii_case(command::OPTION_NNUE_FILE) {
+            self.network = Some(Arc::new(Network::load(value)?));
+            self.send_network();
+        } else {
+            return Err(HaddockError::UnsupportedEngineOption(self.name.clone(), name.to_string()));
+        }
+        Ok(())
+    }
+
+    fn send_network(&mut self) {
+        let network = if self.use_nnue { self.network.clone() } else { None };
+        self.searcher.set_network(network);
+    }
+}
+
+impl Player for SearcherPlayer {
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn new_game(&mut self) -> Result<(), HaddockError> {
+        self.searcher.new_game();
+        Ok(())
+    }
+
+    fn find_best_move(&mut self, fen: &str, moves: &[Move_]) -> Result<Option<Move_>, HaddockError> {
+        let fen_parts = fen.split_whitespace().collect::<Vec<&str>>();
+        let mut record = GameRecord::new(parser::parse_fen(&fen_parts)?);
+        for &mv in moves {
+            record.apply_move(mv);
+        }
+
+        self.searcher.set_position(*record.get_position(), record.get_history().clone());
+        Ok(self.searcher.find_best_move(self.search_type).ok())
+    }
+}
+
+//plays with an external UCI engine, running as a child process
+pub struct UciProcessPlayer {
+    client: UciClient,
+    search_type: SearchType
+}
+
+impl UciProcessPlayer {
+    pub fn new(command: &str, search_type: SearchType, options: &[(String, String)]) -> Result<UciProcessPlayer, HaddockError> {
+        let mut client = UciClient::start(command).map_err(HaddockError::EngineFailure)?;
+        for (name, value) in options {
+            //uci option names are case insensitive
+            if !client.get_options().iter().any(|o| o.name.eq_ignore_ascii_case(name)) {
+                return Err(HaddockError::UnsupportedEngineOption(client.get_name().to_string(), name.to_string()));
+            }
+            client.set_option(name, value).map_err(HaddockError::EngineFailure)?;
+        }
+        client.is_ready().map_err(HaddockError::EngineFailure)?;
+
+        Ok(UciProcessPlayer { client, search_type })
+    }
+}
+
+impl Player for UciProcessPlayer {
+    fn get_name(&self) -> String {
+        self.client.get_name().to_string()
+    }
+
+    fn new_game(&mut self) -> Result<(), HaddockError> {
+        self.client.new_game().map_err(HaddockError::EngineFailure)
+    }
+
+    fn find_best_move(&mut self, fen: &str, moves: &[Move_]) -> Result<Option<Move_>, HaddockError> {
+        self.client.set_position(fen, moves).map_err(HaddockError::EngineFailure)?;
+        self.client.go(self.search_type).map_err(HaddockError::EngineFailure)
+    }
+}
@@ -9,6 +9,7 @@ use crate::piecetype::PieceType;
 use crate::square;
 use crate::square::Square;
 use crate::bitboard::BitBoard;
+use crate::generator::Generator;
 use crate::zobrist;
 
 #[derive(Clone, Copy)]
@@ -19,7 +20,8 @@ pub struct Position {
     enpassant_square: Option<Square>,
     halfmoveclock: u32,
     fullmovenumber: u32,
-    hash: u64
+    hash: u64,
+    pawn_hash: u64
 }
 
 impl Position {
@@ -31,7 +33,8 @@ impl Position {
             enpassant_square: None,
             halfmoveclock: 0,
             fullmovenumber: 0,
-            hash: 0
+            hash: 0,
+            pawn_hash: 0
         }
     }
 
@@ -149,17 +152,24 @@ impl Position {
     }
 
     pub fn is_draw_by_halfmoveclock(&self) -> bool {
-        self.halfmoveclock >= global::MAX_HALFMOVECLOCK
+        self.halfmoveclock >= global::FIFTY_MOVE_RULE_PLIES
     }
 
     fn apply_simple_move(&mut self, square_from: Square, square_to: Square, piece_type: PieceType) {
         self.remove_piece(square_from, piece_type);
-        self.hash ^= zobrist::ZOBRIST_SQUARE_PIECE_KEYS[square_from.to_usize()][piece_type.to_usize()];
-        
+        self.toggle_piece_hash(square_from, piece_type);
 
         self.set_piece(square_to, piece_type);
-        self.hash ^= zobrist::ZOBRIST_SQUARE_PIECE_KEYS[square_to.to_usize()][piece_type.to_usize()];
-        
+        self.toggle_piece_hash(square_to, piece_type);
+    }
+
+    //pawns are also part of the pawn hash, used for the pawn structure cache
+    fn toggle_piece_hash(&mut self, square: Square, piece_type: PieceType) {
+        let key = zobrist::ZOBRIST_SQUARE_PIECE_KEYS[square.to_usize()][piece_type.to_usize()];
+        self.hash ^= key;
+        if piece_type.is_pawn() {
+            self.pawn_hash ^= key;
+        }
     }
 
     fn clear_castling_status(&mut self, index: usize) {
@@ -179,11 +189,14 @@ impl Position {
             None => panic!("No piece found at square {}", square_from.to_fen())
         }
 
+        //ep key that is part of the current hash, must be known before the board changes
+        let enpassant_key = self.get_enpassant_hash_key();
+
         if move_.is_capture() {
             match self.get_piece(square_to) {
                 Some(p) => {
                     self.remove_piece(square_to, p);
-                    self.hash ^= zobrist::ZOBRIST_SQUARE_PIECE_KEYS[square_to.to_usize()][p.to_usize()];
+                    self.toggle_piece_hash(square_to, p);
                 },
                 None => ()
             }
@@ -199,8 +212,8 @@ impl Position {
             
             let cap_square = Square::from_xy(x_cap, y_cap);
             let cap_ptype = PieceType::new_pawn(1 - self.active_color);
-            self.remove_piece(cap_square, PieceType::new_pawn(1 - self.active_color));
-            self.hash ^= zobrist::ZOBRIST_SQUARE_PIECE_KEYS[cap_square.to_usize()][cap_ptype.to_usize()];
+            self.remove_piece(cap_square, cap_ptype);
+            self.toggle_piece_hash(cap_square, cap_ptype);
         }
 
         //promo piece only has type info, not color info
@@ -208,11 +221,10 @@ impl Position {
             let mut promo_piece = move_.get_promo_piece();
             promo_piece.set_color(self.get_active_color());
             self.remove_piece(square_to, piece);
-            self.hash ^= zobrist::ZOBRIST_SQUARE_PIECE_KEYS[square_to.to_usize()][piece.to_usize()];
-            
+            self.toggle_piece_hash(square_to, piece);
+
             self.set_piece(square_to, promo_piece);
-            self.hash ^= zobrist::ZOBRIST_SQUARE_PIECE_KEYS[square_to.to_usize()][promo_piece.to_usize()];
-            
+            self.toggle_piece_hash(square_to, promo_piece);
         }
 
         //castling
@@ -297,14 +309,8 @@ impl Position {
         }
 
         //clear en-passant square
-        match self.enpassant_square {
-            Some(ep_sq) => {
-                let (x, _) = ep_sq.to_xy();
-                self.hash ^= zobrist::ZOBRIST_EP_FILE_KEYS[x as usize];
-                self.enpassant_square = None;
-            },
-            None => ()
-        }
+        self.hash ^= enpassant_key;
+        self.enpassant_square = None;
 
         //set en-passant square
         if piece.is_pawn() {
@@ -313,11 +319,9 @@ impl Position {
 
             if self.active_color == global::COLOR_WHITE && y_from == 1 && y_to == 3 {
                 self.enpassant_square = Some(Square::from_xy(x_from, 2));
-                self.hash ^= zobrist::ZOBRIST_EP_FILE_KEYS[x_from as usize];
             }
             else if self.active_color == global::COLOR_BLACK && y_from == 6 && y_to == 4 {
                 self.enpassant_square = Some(Square::from_xy(x_from, 5));
-                self.hash ^= zobrist::ZOBRIST_EP_FILE_KEYS[x_from as usize];
             }
         }
 
@@ -336,7 +340,9 @@ impl Position {
         //flip color
         self.active_color = 1 - self.active_color;
         self.hash ^= zobrist::ZOBRIST_BLACK_KEY[0];
-        
+
+        //the new ep square can only be checked with the opponent to move
+        self.hash ^= self.get_enpassant_hash_key();
     }
 
     pub fn analyze_move(&self, mut move_: Move_) -> Move_ {
@@ -390,10 +396,15 @@ impl Position {
 
     pub fn generate_new_hash(&mut self) {
         let mut hash: u64 = 0;
+        let mut pawn_hash: u64 = 0;
 
         //pieces
         for (piece, square) in self.get_all_active_pieces() {
-            hash ^= zobrist::ZOBRIST_SQUARE_PIECE_KEYS[square.to_usize()][piece.to_usize()];
+            let key = zobrist::ZOBRIST_SQUARE_PIECE_KEYS[square.to_usize()][piece.to_usize()];
+            hash ^= key;
+            if piece.is_pawn() {
+                pawn_hash ^= key;
+            }
         }
 
         //side to move
@@ -409,20 +420,75 @@ impl Position {
         }
 
         //ep square
-        match self.enpassant_square {
-            Some(sq) => {
-                let (x, _) = sq.to_xy();
-                hash ^= zobrist::ZOBRIST_EP_FILE_KEYS[x as usize];
-            },
-            None => ()
-        }
+        hash ^= self.get_enpassant_hash_key();
 
         self.hash = hash;
+        self.pawn_hash = pawn_hash;
+    }
+
+    //the ep square only counts for the position identity when a legal ep capture exists,
+    //otherwise repetitions after a double pawn step would be missed
+    fn get_enpassant_hash_key(&self) -> u64 {
+        let ep_square = match self.enpassant_square {
+            Some(s) => s,
+            None => return 0
+        };
+
+        let (x, y) = ep_square.to_xy();
+        let (ep_y, pawn_y) = if self.active_color == COLOR_WHITE { (5, 4) } else { (2, 3) };
+        if y != ep_y {
+            return 0;
+        }
+
+        let own_pawn = PieceType::new_pawn(self.active_color);
+        let captured_square = Square::from_xy(x, pawn_y);
+        for x_from in [x.wrapping_sub(1), x + 1].iter().filter(|&&x_from| x_from < 8) {
+            let square_from = Square::from_xy(*x_from, pawn_y);
+            if self.get_piece(square_from) != Some(own_pawn) {
+                continue;
+            }
+
+            //play the capture on the board only, apply_move would need this key again
+            //without a king (test positions) there is no check to worry about
+            let mut pos = *self;
+            pos.remove_piece(square_from, own_pawn);
+            pos.remove_piece(captured_square, PieceType::new_pawn(1 - self.active_color));
+            pos.set_piece(ep_square, own_pawn);
+            if pos.get_piece_count(PieceType::new_king(self.active_color)) == 0 ||
+                !Generator::new(&pos).is_check(self.active_color) {
+                return zobrist::ZOBRIST_EP_FILE_KEYS[x as usize];
+            }
+        }
+        0
+    }
+
+    //colors swapped and board flipped vertically, the same position for the other side
+    #[allow(dead_code)]
+    pub fn get_mirrored(&self) -> Position {
+        let mut mirrored = *self;
+        for piece_value in 0..12 {
+            //piece value is type | color, so the mirrored piece is the other of each pair
+            let board = self.bit_boards[piece_value ^ 1].to_u64();
+            mirrored.bit_boards[piece_value] = BitBoard::from_u64(board.swap_bytes());
+        }
+        mirrored.active_color = 1 - self.active_color;
+        mirrored.castling_status = [self.castling_status[2], self.castling_status[3], self.castling_status[0], self.castling_status[1]];
+        mirrored.enpassant_square = self.enpassant_square.map(|s| {
+            let (x, y) = s.to_xy();
+            Square::from_xy(x, 7 - y)
+        });
+        mirrored.generate_new_hash();
+        mirrored
     }
 
     pub fn get_hash(&self) -> u64 {
         self.hash
     }
+
+    //only the pawns, key of the pawn structure cache
+    pub fn get_pawn_hash(&self) -> u64 {
+        self.pawn_hash
+    }
 }
 
 impl fmt::Display for Position {
@@ -0,0 +1,57 @@
+use crate::error::HaddockError;
+use crate::gamerecord::GameRecord;
+use crate::generator::Generator;
+use crate::move_::Move_;
+use crate::parser;
+use crate::validation;
+
+//parser for the arguments of the uci position command:
+//  startpos [moves <m1> ... <mi>]
+//  fen <fen> [moves <m1> ... <mi>]
+
+//returns the game from the start position up to the position after all moves
+pub fn parse(args: &str) -> Result<GameRecord, HaddockError> {
+    let words = args.split_whitespace().collect::<Vec<&str>>();
+    let moves_index = words.iter().position(|&w| w == "moves").unwrap_or(words.len());
+
+    let position = match words.first() {
+        Some(&"startpos") => {
+            if moves_index > 1 {
+                return Err(HaddockError::UnexpectedToken(words[1].to_string()));
+            }
+            parser::parse_startpos()?
+        }
+        Some(&"fen") => {
+            //a fen has at most 6 fields, the last 4 can be omitted
+            let fen_parts = &words[1..moves_index];
+            if fen_parts.len() > 6 {
+                return Err(HaddockError::UnexpectedToken(fen_parts[6].to_string()));
+            }
+            let position = parser::parse_fen(fen_parts)?;
+            validation::validate_position(&position)?;
+            position
+        }
+        Some(w) if moves_index > 0 => return Err(HaddockError::UnexpectedToken(w.to_string())),
+        _ => return Err(HaddockError::MissingPosition)
+    };
+
+    let mut record = GameRecord::new(position);
+    for &word in words.iter().skip(moves_index + 1) {
+        let position = record.get_position();
+        let mv = Move_::from_str(word)?;
+
+        //analyze_move needs a piece on the from square
+        if position.get_piece(mv.get_squares().0).is_none() {
+            return Err(HaddockError::IllegalMove(word.to_string()));
+        }
+
+        let mv = position.analyze_move(mv);
+        if !Generator::new(position).is_legal_move(mv) {
+            return Err(HaddockError::IllegalMove(word.to_string()));
+        }
+
+        record.apply_move(mv);
+    }
+
+    Ok(record)
+}
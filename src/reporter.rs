@@ -0,0 +1,82 @@
+use crate::logger::Logger;
+use crate::searchevent::{SearchEvent, SearchInfo};
+use crate::transposition_table::Bound;
+
+//receives the events of a running search
+pub trait Reporter: Send {
+    fn report(&mut self, event: SearchEvent);
+}
+
+//sends uci info lines to the gui
+pub struct UciReporter {
+    logger: Logger
+}
+
+impl UciReporter {
+    pub fn new(logger: Logger) -> UciReporter {
+        UciReporter { logger }
+    }
+}
+
+impl Reporter for UciReporter {
+    fn report(&mut self, event: SearchEvent) {
+        match event {
+            SearchEvent::Debug(message) => self.logger.debug(&message),
+            _ => self.logger.send(&format_event(&event))
+        }
+    }
+}
+
+//discards all events, e.g. for searches in engine matches
+pub struct NullReporter;
+
+impl Reporter for NullReporter {
+    fn report(&mut self, _event: SearchEvent) {}
+}
+
+pub fn format_event(event: &SearchEvent) -> String {
+    match event {
+        SearchEvent::Info(info) => format_info(info),
+        SearchEvent::CurrMove(depth, move_, number) => {
+            format!("info depth {} currmove {} currmovenumber {}", depth, move_.to_fen(), number)
+        }
+        SearchEvent::Heartbeat(time, nodes, hashfull) => {
+            format!("info time {} nodes {} nps {} hashfull {}", time, nodes, get_nps(*nodes, *time), hashfull)
+        }
+        SearchEvent::BestMove(Some(move_)) => format!("bestmove {}", move_.to_fen()),
+        SearchEvent::BestMove(None) => "bestmove 0000".to_string(),
+        SearchEvent::Debug(message) => format!("info string {}", message)
+    }
+}
+
+fn format_info(info: &SearchInfo) -> String {
+    let mut line = format!(
+        "info depth {} seldepth {} multipv {} score {}",
+        info.depth, info.seldepth, info.multipv, info.score.to_uci_score(info.active_color)
+    );
+
+    match info.bound {
+        Bound::Lower => line.push_str(" lowerbound"),
+        Bound::Upper => line.push_str(" upperbound"),
+        Bound::Exact => ()
+    }
+
+    line.push_str(&format!(
+        " time {} nodes {} nps {} hashfull {} tbhits {}",
+        info.time, info.nodes, get_nps(info.nodes, info.time), info.hashfull, info.tbhits
+    ));
+
+    if !info.pv.is_empty() {
+        line.push_str(" pv");
+        for mv in info.pv.iter() {
+            line.push(' ');
+            line.push_str(&mv.to_fen());
+        }
+    }
+
+    line
+}
+
+fn get_nps(nodes: u64, time: u64) -> u64 {
+    (nodes * 1000).checked_div(time).unwrap_or(nodes)
+}
@@ -0,0 +1,122 @@
+use crate::generator::Generator;
+use crate::move_::Move_;
+use crate::position::Position;
+
+//standard algebraic notation, used for reading and writing PGN
+pub fn move_to_san(position: &Position, move_: Move_) -> String {
+    let generator = Generator::new(position);
+    let legal_moves = generator.generate_legal_moves();
+    let mut san = get_san_without_suffix(position, move_, &legal_moves);
+
+    //check or mate suffix
+    let mut pos = *position;
+    pos.apply_move(move_);
+    let gen = Generator::new(&pos);
+    if gen.is_check(pos.get_active_color()) {
+        if gen.generate_legal_moves().is_empty() {
+            san.push('#');
+        } else {
+            san.push('+');
+        }
+    }
+
+    san
+}
+
+pub fn parse_san(position: &Position, san: &str) -> Option<Move_> {
+    let clean_san = clean_san(san);
+    if clean_san.is_empty() {
+        return None;
+    }
+
+    let generator = Generator::new(position);
+    let legal_moves = generator.generate_legal_moves();
+
+    for &mv in legal_moves.iter() {
+        if get_san_without_suffix(position, mv, &legal_moves) == clean_san {
+            return Some(mv);
+        }
+    }
+
+    //fallback for long algebraic notation (e2e4)
+    if let Ok(mv) = Move_::from_str(&san.to_lowercase()) {
+        let fen = mv.to_fen();
+        return legal_moves.into_iter().find(|m| m.to_fen() == fen);
+    }
+
+    None
+}
+
+fn clean_san(san: &str) -> String {
+    let mut result = san.trim_end_matches(&['+', '#', '!', '?'][..]).to_string();
+    //castling is sometimes written with zeros
+    if result == "0-0" {
+        result = "O-O".to_string();
+    } else if result == "0-0-0" {
+        result = "O-O-O".to_string();
+    }
+    result
+}
+
+fn get_san_without_suffix(position: &Position, move_: Move_, legal_moves: &[Move_]) -> String {
+    let (square_from, square_to) = move_.get_squares();
+
+    if move_.is_castling() {
+        let (x_to, _) = square_to.to_xy();
+        if x_to == 6 {
+            return "O-O".to_string();
+        }
+        return "O-O-O".to_string();
+    }
+
+    let piece = match position.get_piece(square_from) {
+        Some(p) => p,
+        None => return move_.to_fen()
+    };
+
+    let mut san = "".to_string();
+
+    if piece.is_pawn() {
+        if move_.is_capture() {
+            let from_fen = square_from.to_fen();
+            san.push_str(&from_fen[0..1]);
+        }
+    } else {
+        san.push(piece.to_char().to_ascii_uppercase());
+
+        //disambiguation: other pieces of the same type that can go to the same square
+        let others = legal_moves
+            .iter()
+            .filter(|&&m| {
+                let (f, t) = m.get_squares();
+                t == square_to && f != square_from && position.get_piece(f) == Some(piece)
+            })
+            .map(|m| m.get_squares().0)
+            .collect::<Vec<_>>();
+
+        if !others.is_empty() {
+            let (x_from, y_from) = square_from.to_xy();
+            let from_fen = square_from.to_fen();
+            if others.iter().all(|s| s.to_xy().0 != x_from) {
+                san.push_str(&from_fen[0..1]);
+            } else if others.iter().all(|s| s.to_xy().1 != y_from) {
+                san.push_str(&from_fen[1..2]);
+            } else {
+                san.push_str(&from_fen);
+            }
+        }
+    }
+
+    if move_.is_capture() {
+        san.push('x');
+    }
+
+    san.push_str(&square_to.to_fen());
+
+    if move_.is_promotion() {
+        san.push('=');
+        san.push(move_.get_promo_piece().to_char().to_ascii_uppercase());
+    }
+
+    san
+}
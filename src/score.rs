@@ -0,0 +1,67 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+pub const PHASE_MAX: i32 = 256;
+
+//an evaluation term as middlegame and endgame value, mixed by the game phase
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Score {
+    pub mg: i32,
+    pub eg: i32
+}
+
+impl Score {
+    pub const fn new(mg: i32, eg: i32) -> Score {
+        Score { mg, eg }
+    }
+
+    //phase PHASE_MAX is the opening, 0 the bare endgame
+    pub fn taper(self, phase: i32) -> i32 {
+        (self.mg * phase + self.eg * (PHASE_MAX - phase)) / PHASE_MAX
+    }
+}
+
+impl Add for Score {
+    type Output = Score;
+
+    fn add(self, rhs: Score) -> Score {
+        Score::new(self.mg + rhs.mg, self.eg + rhs.eg)
+    }
+}
+
+impl AddAssign for Score {
+    fn add_assign(&mut self, rhs: Score) {
+        self.mg += rhs.mg;
+        self.eg += rhs.eg;
+    }
+}
+
+impl Sub for Score {
+    type Output = Score;
+
+    fn sub(self, rhs: Score) -> Score {
+        Score::new(self.mg - rhs.mg, self.eg - rhs.eg)
+    }
+}
+
+impl SubAssign for Score {
+    fn sub_assign(&mut self, rhs: Score) {
+        self.mg -= rhs.mg;
+        self.eg -= rhs.eg;
+    }
+}
+
+impl Neg for Score {
+    type Output = Score;
+
+    fn neg(self) -> Score {
+        Score::new(-self.mg, -self.eg)
+    }
+}
+
+impl Mul<i32> for Score {
+    type Output = Score;
+
+    fn mul(self, rhs: i32) -> Score {
+        Score::new(self.mg * rhs, self.eg * rhs)
+    }
+}
@@ -1,6 +1,18 @@
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+
+use crate::evalparams::EvalParams;
+use crate::hash_counter::HashCounter;
+use crate::nnue::Network;
+use crate::position::Position;
 use crate::searchtype::SearchType;
 
 pub enum SearchCommand {
     Quit,
-    FindBestMove(SearchType)
-}
\ No newline at end of file
+    NewGame,
+    SetContempt(i32), //centipawns, from the root side
+    SetEvalParams(Box<EvalParams>),
+    SetNetwork(Option<Arc<Network>>), //none for the hand crafted evaluation
+    FindBestMove(Position, HashCounter, SearchType), //root position, positions played before it, limits
+    Ping(Sender<()>) //answered when all commands sent before it are done
+}
@@ -1,14 +1,18 @@
 extern crate rand;
 
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::mpsc::Receiver;
+use std::sync::mpsc::{Receiver, Sender};
 use std::sync::Arc;
 use std::time::{Duration, SystemTime};
 
+use crate::error::HaddockError;
 use crate::global;
+use crate::evalparams::EvalParams;
 use crate::evaluation;
+use crate::evaluation::Evaluator;
 use crate::generator::Generator;
 use crate::move_::Move_;
+use crate::nnue::{ Accumulator, Network };
 use crate::outcome::{ MATE_AGAINST_WHITE, MATE_AGAINST_BLACK, Outcome };
 use crate::position::Position;
 use crate::searchcommand::SearchCommand;
@@ -16,6 +20,13 @@ use crate::searchtype::SearchType;
 use crate::moveresult::MoveResult;
 use crate::hash_counter::HashCounter;
 use crate::transposition_table::{ TranspositionTable, Bound };
+use crate::reporter::{ Reporter, NullReporter };
+use crate::searchevent::{ SearchEvent, SearchInfo };
+
+const CURRMOVE_MIN_TIME_MS: u64 = 1000;
+const HEARTBEAT_INTERVAL_MS: u64 = 1000;
+const MAX_KILLER_DEPTH: usize = 64;
+const MAX_HISTORY_SCORE: u32 = 1_000_000;
 
 pub struct Searcher {
     receiver: Receiver<SearchCommand>,
@@ -28,7 +39,18 @@ pub struct Searcher {
     history: HashCounter,
     transposition_table: TranspositionTable,
     actual_bounds: [Option<Outcome>; 2],
-    transposition_hits: i32
+    transposition_hits: i32,
+    reporter: Box<dyn Reporter>,
+    sel_depth: usize,
+    last_heartbeat: u64,
+    killer_moves: Vec<[Option<Move_>; 2]>,
+    history_scores: Vec<u32>,
+    contempt: i32,
+    draw_value: i32,
+    best_score: Option<Outcome>,
+    evaluator: Evaluator,
+    network: Option<Arc<Network>>,
+    accumulators: Vec<Accumulator> //per depth, only with a network
 }
 
 struct RecursiveSearchRequest<'a> {
@@ -57,48 +79,156 @@ impl Searcher {
             history,
             transposition_table: TranspositionTable::new(),
             actual_bounds: [None; 2],
-            transposition_hits: 0
+            transposition_hits: 0,
+            reporter: Box::new(NullReporter),
+            sel_depth: 0,
+            last_heartbeat: 0,
+            killer_moves: vec![[None; 2]; MAX_KILLER_DEPTH],
+            history_scores: vec![0; 2 * 64 * 64],
+            contempt: 0,
+            draw_value: 0,
+            best_score: None,
+            evaluator: Evaluator::new(),
+            network: None,
+            accumulators: Vec::new()
+        }
+    }
+
+    //the searcher lives for the whole game, tables are kept between moves
+    pub fn new_game(&mut self) {
+        self.transposition_table.clear();
+        self.evaluator.clear();
+        self.history.clear();
+        for k in self.killer_moves.iter_mut() {
+            *k = [None; 2];
         }
+        for h in self.history_scores.iter_mut() {
+            *h = 0;
+        }
+    }
+
+    //root position of the next search and the positions played before it
+    pub fn set_position(&mut self, position: Position, history: HashCounter) {
+        self.base_position = position;
+        self.history = history;
     }
 
-    pub fn start(&mut self) {
+    //centipawns a draw is worth less than equality for the side to move at the root
+    pub fn set_contempt(&mut self, contempt: i32) {
+        self.contempt = contempt;
+    }
+
+    //stored scores were found with the old weights, so the table is cleared
+    pub fn set_eval_params(&mut self, params: EvalParams) {
+        self.evaluator.set_params(params);
+        self.transposition_table.clear();
+    }
+
+    //positions are evaluated by the network instead of the hand crafted evaluation, none switches back
+    pub fn set_network(&mut self, network: Option<Arc<Network>>) {
+        self.network = network;
+        self.transposition_table.clear();
+    }
+
+    pub fn set_reporter(&mut self, reporter: Box<dyn Reporter>) {
+        self.reporter = reporter;
+    }
+
+    //worker loop, runs for the lifetime of the game
+    //every finished search is signalled, also when it was stopped
+    pub fn start(&mut self, finished: &Sender<()>) {
         loop {
             let command = self.receiver.recv().unwrap();
-            if !self.handle_command(&command) {
+            if !self.handle_command(command, finished) {
                 break;
             }
         }
     }
 
-    fn handle_command(&mut self, command: &SearchCommand) -> bool {
+    fn handle_command(&mut self, command: SearchCommand, finished: &Sender<()>) -> bool {
         match command {
             SearchCommand::Quit => return false,
-            SearchCommand::FindBestMove(search_type) => {
-                self.search_type = Some(*search_type);
-                self.handle_command_find_best_move();
+            SearchCommand::NewGame => {
+                self.new_game();
+                self.debug("tables cleared for new game".to_string());
+            }
+            SearchCommand::SetContempt(contempt) => self.set_contempt(contempt),
+            SearchCommand::SetEvalParams(params) => self.set_eval_params(*params),
+            SearchCommand::SetNetwork(network) => self.set_network(network),
+            SearchCommand::FindBestMove(position, history, search_type) => {
+                self.handle_command_find_best_move(position, history, search_type);
+                finished.send(()).expect("Error while sending search finished");
             }
+            SearchCommand::Ping(ack) => ack.send(()).expect("Error while sending ping answer")
         }
         true
     }
 
-    fn handle_command_find_best_move(&mut self) {
+    fn handle_command_find_best_move(&mut self, position: Position, history: HashCounter, search_type: SearchType) {
+        self.set_position(position, history);
+        match self.find_best_move(search_type) {
+            Ok(best_move) => self.reporter.report(SearchEvent::BestMove(Some(best_move))),
+            Err(e) => {
+                self.debug(e.to_string());
+                self.reporter.report(SearchEvent::BestMove(None));
+            }
+        }
+    }
+
+    //synchronous search, used by the command loop and by bench
+    //fails only when there is no legal move (mate or stalemate)
+    pub fn find_best_move(&mut self, search_type: SearchType) -> Result<Move_, HaddockError> {
+        self.search_type = Some(search_type);
+
         let mut max_depth = 1000;
         match self.search_type {
             Some(SearchType::Depth(n)) => max_depth = n,
             _ => (),
         }
 
-        let best_move = self.search_tree_3(max_depth);
-        println!("bestmove {}", best_move.to_fen());
+        self.search_tree_3(max_depth)
+    }
+
+    //quiescence search with an open window, the variant leads to the quiet position the score comes from
+    //none when the search was stopped
+    pub fn quiesce(&mut self) -> Option<(Outcome, Vec<Move_>)> {
+        self.search_type = Some(SearchType::Infinite);
+        self.node_count = 0;
+        self.last_heartbeat = 0;
+        self.set_times();
+        let position = self.base_position;
+        self.set_draw_value(&position);
+        self.set_root_accumulator(&position);
+
+        let request = RecursiveSearchRequest {
+            position: &position,
+            depth: 0,
+            horizon: 0,
+            bounds: [None; 2]
+        };
+        self.quiescence_search(request).map(|r| (r.score, r.variant))
+    }
+
+    //score of the last finished iteration of the last search, from white's side
+    pub fn get_best_score(&self) -> Option<Outcome> {
+        self.best_score
     }
 
-    fn search_tree_3(&mut self, max_depth: u64) -> Move_ {
+    pub fn get_node_count(&self) -> u32 {
+        self.node_count
+    }
+
+    fn search_tree_3(&mut self, max_depth: u64) -> Result<Move_, HaddockError> {
 
         self.node_count = 0;
+        self.last_heartbeat = 0;
         self.set_times();
         let current_pos = self.base_position.clone();
+        self.set_root_accumulator(&current_pos);
+        self.set_draw_value(&current_pos);
 
         let mut best_move: Option<Move_> = None;
+        self.best_score = None;
 
         let mut last_scores: [Option<Outcome>; 2] = [None; 2];
 
@@ -131,9 +261,18 @@ impl Searcher {
                 }
             }
 
+            //bounds from stale table entries can cross, nothing would be cut off in such a window
+            if let (Some(lower), Some(upper)) = (bounds[0], bounds[1]) {
+                if lower >= upper {
+                    bounds = [None; 2];
+                }
+            }
+
+            self.sel_depth = 0;
+
             let mut response_;
             loop {
-                println!("starting search with aspiration white lbound: {:?}, black ubound: {:?}", bounds[0], bounds[1]);
+                self.debug(format!("starting search with aspiration white lbound: {:?}, black ubound: {:?}", bounds[0], bounds[1]));
                 self.actual_bounds = [None; 2];
 
                 let request = RecursiveSearchRequest {
@@ -148,10 +287,13 @@ impl Searcher {
                     break;
                 }
 
-                println!("actual white lbound: {:?}, black ubound: {:?}", self.actual_bounds[0], self.actual_bounds[1]);
+                self.debug(format!("actual white lbound: {:?}, black ubound: {:?}", self.actual_bounds[0], self.actual_bounds[1]));
 
                 if max_iter_depth > 2 {
                     let mut search_again = false;
+                    let failed_bounds = bounds;
+                    let failed_low = self.actual_bounds[0].is_none();
+                    let failed_high = self.actual_bounds[1].is_none();
 
                     if self.actual_bounds[0].is_none() {
                         search_again = true;
@@ -181,7 +323,8 @@ impl Searcher {
                     }
 
                     if let Some(response) = &response_ {
-                        if response.variant.len() == 0 && !search_again {
+                        //with both bounds open there is nothing left to widen
+                        if response.variant.len() == 0 && !search_again && (bounds[0].is_some() || bounds[1].is_some()) {
                             search_again = true;
                             //increase both sides??
                             if let Some(Outcome::Undecided(d, material_value)) = bounds[0] {
@@ -207,6 +350,7 @@ impl Searcher {
                     }
 
                     if search_again {
+                        self.report_aspiration_fail(&current_pos, max_iter_depth, failed_bounds, failed_low, failed_high);
                         continue;
                     }
                 }
@@ -215,7 +359,7 @@ impl Searcher {
             }
 
             //println!("history table size: {}", self.history.get_len());
-            println!("transposition table size: {}, hits: {}", self.transposition_table.len(), self.transposition_hits);
+            self.debug(format!("transposition table size: {}, hits: {}", self.transposition_table.len(), self.transposition_hits));
 
             if self.must_stop() {
                 break;
@@ -226,19 +370,13 @@ impl Searcher {
                 last_scores[0] = Some(response.score);
 
                 let time = self.get_time_elapsed_ms();
-                let mut nps = self.node_count as u64;
-                if time > 0 {
-                    nps = nps * 1000 / time;
-                }
+                let info = self.create_search_info(&current_pos, max_iter_depth, response.score, Bound::Exact, response.variant.clone());
+                self.reporter.report(SearchEvent::Info(info));
 
-                let uci_score = response.score.to_uci_score(current_pos.get_active_color());
-                let pv_string = Searcher::get_moves_string(&response.variant);
-
-                println!(
-                    "info depth {} score {} time {} nodes {} nps {} pv {}",
-                    max_iter_depth, uci_score, time, self.node_count, nps, pv_string
-                );
-                best_move = Some(response.variant[0]);
+                if let Some(&mv) = response.variant.first() {
+                    best_move = Some(mv);
+                    self.best_score = Some(response.score);
+                }
                 if response.score.end() {
                     break;
                 }
@@ -256,9 +394,150 @@ impl Searcher {
 
         }
 
+        //stopped before the first iteration finished, any legal move will do
         match best_move {
-            Some(m) => return m,
-            None => panic!("Best move not found!")
+            Some(m) => Ok(m),
+            None => Generator::new(&current_pos).generate_legal_moves().first().cloned().ok_or(HaddockError::NoLegalMove)
+        }
+    }
+
+    fn get_move_order_score(&self, mv: Move_, tt_move: Option<Move_>, depth: usize, active_color: u8) -> u32 {
+        if Some(mv) == tt_move {
+            return u32::MAX;
+        }
+        if mv.is_capture() || mv.is_promotion() {
+            return MAX_HISTORY_SCORE + 3;
+        }
+        if depth < MAX_KILLER_DEPTH {
+            if self.killer_moves[depth][0] == Some(mv) {
+                return MAX_HISTORY_SCORE + 2;
+            }
+            if self.killer_moves[depth][1] == Some(mv) {
+                return MAX_HISTORY_SCORE + 1;
+            }
+        }
+        self.history_scores[Searcher::get_history_index(mv, active_color)]
+    }
+
+    //a quiet move caused a cutoff: remember it as killer move and raise its history score
+    fn update_quiet_cutoff(&mut self, mv: Move_, depth: usize, horizon: usize, active_color: u8) {
+        if depth < MAX_KILLER_DEPTH && self.killer_moves[depth][0] != Some(mv) {
+            self.killer_moves[depth][1] = self.killer_moves[depth][0];
+            self.killer_moves[depth][0] = Some(mv);
+        }
+
+        let index = Searcher::get_history_index(mv, active_color);
+        self.history_scores[index] = std::cmp::min(self.history_scores[index] + (horizon * horizon) as u32, MAX_HISTORY_SCORE);
+    }
+
+    fn get_history_index(mv: Move_, active_color: u8) -> usize {
+        let (square_from, square_to) = mv.get_squares();
+        (active_color as usize * 64 + square_from.to_usize()) * 64 + square_to.to_usize()
+    }
+
+    //draws are scored from white's side, so contempt depends on the root side
+    fn set_draw_value(&mut self, position: &Position) {
+        self.draw_value = if position.get_active_color() == global::COLOR_WHITE { -self.contempt } else { self.contempt };
+    }
+
+    //draw scores are stored without contempt, so the table stays valid when the root side changes
+    fn get_table_outcome(outcome: Outcome) -> Outcome {
+        match outcome {
+            Outcome::Draw(depth, _) => Outcome::Draw(depth, 0),
+            _ => outcome
+        }
+    }
+
+    fn get_outcome_with_contempt(&self, outcome: Outcome) -> Outcome {
+        match outcome {
+            Outcome::Draw(depth, _) => Outcome::Draw(depth, self.draw_value),
+            _ => outcome
+        }
+    }
+
+    //the search updates the accumulators of the other depths from this one
+    fn set_root_accumulator(&mut self, position: &Position) {
+        if let Some(network) = &self.network {
+            self.accumulators.clear();
+            self.accumulators.push(network.get_accumulator(position));
+        }
+    }
+
+    //accumulator of a position at depth + 1 from the one of its parent at depth
+    fn update_accumulator(&mut self, depth: usize, parent: &Position, child: &Position) {
+        if let Some(network) = &self.network {
+            let mut accumulator = self.accumulators[depth];
+            network.update_accumulator(&mut accumulator, parent, child);
+            self.accumulators.truncate(depth + 1);
+            self.accumulators.push(accumulator);
+        }
+    }
+
+    fn evaluate(&mut self, position: &Position, depth: usize) -> Outcome {
+        match &self.network {
+            Some(network) => evaluation::evaluate_network(position, depth as i32, network, &self.accumulators[depth]),
+            None => self.evaluator.evaluate(position, depth as i32)
+        }
+    }
+
+    //draw by repetition, halfmove clock or stalemate, seen with contempt
+    fn get_draw_outcome(&self, depth: usize) -> Outcome {
+        Outcome::Draw(depth as i32, self.draw_value)
+    }
+
+    fn debug(&mut self, message: String) {
+        self.reporter.report(SearchEvent::Debug(message));
+    }
+
+    //leaving a child position during search
+    fn history_decr(&mut self, hash: u64) {
+        if !self.history.decr(hash) {
+            self.debug(format!("hash {:X} not found while decr", hash));
+        }
+    }
+
+    fn create_search_info(&self, position: &Position, depth: usize, score: Outcome, bound: Bound, pv: Vec<Move_>) -> SearchInfo {
+        SearchInfo {
+            depth,
+            seldepth: self.sel_depth,
+            multipv: 1,
+            score,
+            active_color: position.get_active_color(),
+            bound,
+            time: self.get_time_elapsed_ms(),
+            nodes: self.node_count as u64,
+            hashfull: self.transposition_table.get_hashfull(),
+            tbhits: 0,
+            pv
+        }
+    }
+
+    //scores are from white's perspective, uci bounds from the perspective of the side to move
+    fn report_aspiration_fail(&mut self, position: &Position, depth: usize, bounds: [Option<Outcome>; 2], failed_low: bool, failed_high: bool) {
+        let white = position.get_active_color() == global::COLOR_WHITE;
+
+        let (score, bound) = if failed_low && bounds[0].is_some() {
+            (bounds[0].unwrap(), if white { Bound::Upper } else { Bound::Lower })
+        } else if failed_high && bounds[1].is_some() {
+            (bounds[1].unwrap(), if white { Bound::Lower } else { Bound::Upper })
+        } else {
+            return;
+        };
+
+        let info = self.create_search_info(position, depth, score, bound, Vec::new());
+        self.reporter.report(SearchEvent::Info(info));
+    }
+
+    //periodic node count and speed, checked every 64k nodes
+    fn check_heartbeat(&mut self) {
+        if self.node_count & 0xFFFFu32 != 0 {
+            return;
+        }
+
+        let time = self.get_time_elapsed_ms();
+        if time >= self.last_heartbeat + HEARTBEAT_INTERVAL_MS {
+            self.last_heartbeat = time;
+            self.reporter.report(SearchEvent::Heartbeat(time, self.node_count as u64, self.transposition_table.get_hashfull()));
         }
     }
 
@@ -268,6 +547,10 @@ impl Searcher {
         }
 
         self.node_count += 1;
+        self.check_heartbeat();
+        if request.depth > self.sel_depth {
+            self.sel_depth = request.depth;
+        }
 
         let active_color = request.position.get_active_color();
         let other_color = 1 - active_color;
@@ -276,6 +559,7 @@ impl Searcher {
         if request.depth > 0 {
             if let Some((mv, score, bound)) = self.transposition_table.get(request.position.get_hash(), request.horizon as i32) {
                 self.transposition_hits += 1;
+                let score = self.get_outcome_with_contempt(score);
 
                 match bound {
                     Bound::Exact => {
@@ -304,6 +588,8 @@ impl Searcher {
         }
 
         if request.horizon == 0 {
+            //the same position one ply deeper
+            self.update_accumulator(request.depth, request.position, request.position);
             let quiescence_request = RecursiveSearchRequest {
                 position: request.position,
                 depth: request.depth + 1,
@@ -317,7 +603,7 @@ impl Searcher {
         //null move evaluation
         //TODO don't do this when in zugzwang
         if request.depth > 3 && request.horizon == 1 {
-            let score = Some(evaluation::evaluate(&request.position, request.depth as i32));
+            let score = Some(self.evaluate(request.position, request.depth));
             if Searcher::is_better_outcome(&score, &request.bounds[active_color as usize], active_color) {
                 //cutoff
                 if Searcher::is_better_or_equal_outcome(&request.bounds[other_color as usize], &score, other_color) {
@@ -336,16 +622,13 @@ impl Searcher {
         let mut current_best_variant: Vec<Move_> = Vec::new();
         let mut moves = generator.generate_moves(false);
 
-        //sort moves; killer move first
-        if let Some(mv) = self.transposition_table.get_best_move(request.position.get_hash()) {
-            if let Some(p) = moves.iter().position(|&m| m == mv) {
-                moves.remove(p);
-                moves.insert(0, mv);
-            }
-        }
+        //sort moves; best move from transposition table, captures, killer moves, history
+        let tt_move = self.transposition_table.get_best_move(request.position.get_hash());
+        moves.sort_by_key(|&mv| std::cmp::Reverse(self.get_move_order_score(mv, tt_move, request.depth, active_color)));
 
         let mut has_valid_moves = false;
         let mut alpha_updated = false;
+        let mut move_number = 0;
 
         for mv in moves {
             if has_valid_moves && request.depth > 3 && request.horizon == 1 && !(mv.is_capture() || mv.is_promotion()) {
@@ -358,6 +641,11 @@ impl Searcher {
             match generator.try_apply_move(mv, &self.history) {
                 MoveResult::Next(child_pos) => {
                     has_valid_moves = true;
+                    move_number += 1;
+                    if request.depth == 0 && self.get_time_elapsed_ms() >= CURRMOVE_MIN_TIME_MS {
+                        self.reporter.report(SearchEvent::CurrMove(request.horizon, mv, move_number));
+                    }
+                    self.update_accumulator(request.depth, request.position, &child_pos);
                     let child_request = RecursiveSearchRequest {
                         position: &child_pos,
                         depth: request.depth + 1,
@@ -366,18 +654,18 @@ impl Searcher {
                     };
                     self.history.incr(child_pos.get_hash());
                     if let Some(child_response) = self.recursive_search(child_request) {
-                        self.history.decr(child_pos.get_hash());
+                        self.history_decr(child_pos.get_hash());
                         score = Some(child_response.score);
                         variant = child_response.variant;
                     } else {
-                        self.history.decr(child_pos.get_hash());
+                        self.history_decr(child_pos.get_hash());
                         return None;
                     }
                 },
                 MoveResult::Illegal => continue,
                 MoveResult::Draw => {
                     has_valid_moves = true;
-                    score = Some(Outcome::Draw(request.depth as i32));
+                    score = Some(self.get_draw_outcome(request.depth));
                 }
             }
 
@@ -396,9 +684,12 @@ impl Searcher {
                         request.position.get_hash(),
                         request.horizon as i32,
                         mv,
-                        score.unwrap(),
+                        Searcher::get_table_outcome(score.unwrap()),
                         Bound::Lower
                     );
+                    if !(mv.is_capture() || mv.is_promotion()) {
+                        self.update_quiet_cutoff(mv, request.depth, request.horizon, active_color);
+                    }
 
                     return Some(RecursiveSearchResponse {
                         score : request.bounds[other_color as usize].unwrap(),
@@ -421,7 +712,7 @@ impl Searcher {
                     score = Some(Outcome::BlackIsMate(request.depth as i32));
                 }
             } else {
-                score = Some(Outcome::Draw(request.depth as i32));
+                score = Some(self.get_draw_outcome(request.depth));
             }
             if Searcher::is_better_outcome(&score, &request.bounds[active_color as usize], active_color) {
                 if request.depth == 0 || request.depth == 1 {
@@ -448,7 +739,7 @@ impl Searcher {
                     request.position.get_hash(),
                     request.horizon as i32,
                     current_best_variant[0],
-                    current_best_score.unwrap(),
+                    Searcher::get_table_outcome(current_best_score.unwrap()),
                     Bound::Exact
                 );
             }
@@ -467,13 +758,17 @@ impl Searcher {
         }
 
         self.node_count += 1;
+        self.check_heartbeat();
+        if request.depth > self.sel_depth {
+            self.sel_depth = request.depth;
+        }
 
         let active_color = request.position.get_active_color();
         let other_color = 1 - active_color;
         let generator = Generator::new(request.position);
 
 
-        let score = Some(evaluation::evaluate(&request.position, request.depth as i32));
+        let score = Some(self.evaluate(request.position, request.depth));
         if Searcher::is_better_outcome(&score, &request.bounds[active_color as usize], active_color) {
             //cutoff
             if Searcher::is_better_or_equal_outcome(&request.bounds[other_color as usize], &score, other_color) {
@@ -485,6 +780,9 @@ impl Searcher {
             request.bounds[active_color as usize] = score;
         }
 
+        //moves to the quiet position the score comes from
+        let mut variant = Vec::new();
+
         //generate captures
         let mut moves = generator.generate_moves(true);
 
@@ -498,8 +796,10 @@ impl Searcher {
 
         for mv in moves {
             let score: Option<Outcome>;
+            let child_variant: Vec<Move_>;
             match generator.try_apply_move(mv, &self.history) {
                 MoveResult::Next(child_pos) => {
+                    self.update_accumulator(request.depth, request.position, &child_pos);
                     let child_request = RecursiveSearchRequest {
                         position: &child_pos,
                         depth: request.depth + 1,
@@ -508,16 +808,18 @@ impl Searcher {
                     };
                     self.history.incr(child_pos.get_hash());
                     if let Some(child_response) = self.quiescence_search(child_request) {
-                        self.history.decr(child_pos.get_hash());
+                        self.history_decr(child_pos.get_hash());
                         score = Some(child_response.score);
+                        child_variant = child_response.variant;
                     } else {
-                        self.history.decr(child_pos.get_hash());
+                        self.history_decr(child_pos.get_hash());
                         return None;
                     }
                 },
                 MoveResult::Illegal => continue,
                 MoveResult::Draw => {
-                    score = Some(Outcome::Draw(request.depth as i32));
+                    score = Some(self.get_draw_outcome(request.depth));
+                    child_variant = Vec::new();
                 }
             }
 
@@ -530,12 +832,14 @@ impl Searcher {
                     });
                 }
                 request.bounds[active_color as usize] = score;
+                variant = vec![mv];
+                variant.extend(child_variant);
             }
         }
 
         return Some(RecursiveSearchResponse {
             score : request.bounds[active_color as usize].unwrap(),
-            variant: Vec::new()
+            variant
         });
     }
 
@@ -596,6 +900,12 @@ impl Searcher {
     }
 
     fn must_stop(&self) -> bool {
+        if let Some(SearchType::Nodes(n)) = self.search_type {
+            if self.node_count as u64 >= n {
+                return true;
+            }
+        }
+
         if self.node_count & 0xFFFFu32 != 0 {
             return false;
         }
@@ -662,15 +972,4 @@ impl Searcher {
             return score.unwrap() <= current_best_score.unwrap();
         }
     }
-
-    fn get_moves_string(moves: &Vec<Move_>) -> String {
-        let mut moves_string = "".to_string();
-        for mv in moves {
-            if moves_string.len() > 0 {
-                moves_string.push_str(" ");
-            }
-            moves_string.push_str(&mv.to_fen());
-        }
-        moves_string
-    }
 }
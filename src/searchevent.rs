@@ -0,0 +1,28 @@
+use crate::move_::Move_;
+use crate::outcome::Outcome;
+use crate::transposition_table::Bound;
+
+//result of a (possibly failed) search iteration
+#[derive(Clone)]
+pub struct SearchInfo {
+    pub depth: usize,
+    pub seldepth: usize,
+    pub multipv: usize,
+    pub score: Outcome,
+    pub active_color: u8,
+    pub bound: Bound,
+    pub time: u64,
+    pub nodes: u64,
+    pub hashfull: u32,
+    pub tbhits: u64,
+    pub pv: Vec<Move_>
+}
+
+#[derive(Clone)]
+pub enum SearchEvent {
+    Info(SearchInfo),
+    CurrMove(usize, Move_, usize), //depth, move, move number (1 based)
+    Heartbeat(u64, u64, u32), //time, nodes, hashfull
+    BestMove(Option<Move_>), //none when there is no legal move
+    Debug(String) //diagnostics, only shown in debug mode
+}
@@ -5,4 +5,23 @@ pub enum SearchType {
     Depth(u64),
     Nodes(u64),
     MoveTime(u64)
+}
+
+impl SearchType {
+    //arguments for the UCI go command
+    pub fn to_go_args(&self) -> String {
+        match self {
+            SearchType::Infinite => "infinite".to_string(),
+            SearchType::CTime(wtime, btime, winc, binc, movestogo) => {
+                let mut args = format!("wtime {} btime {} winc {} binc {}", wtime, btime, winc, binc);
+                if *movestogo > 0 {
+                    args.push_str(&format!(" movestogo {}", movestogo));
+                }
+                args
+            }
+            SearchType::Depth(depth) => format!("depth {}", depth),
+            SearchType::Nodes(nodes) => format!("nodes {}", nodes),
+            SearchType::MoveTime(move_time) => format!("movetime {}", move_time)
+        }
+    }
 }
\ No newline at end of file
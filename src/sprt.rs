@@ -0,0 +1,124 @@
+//match statistics: Elo difference with error bars and the sequential probability ratio test
+
+#[derive(Clone, Copy)]
+pub struct Sprt {
+    pub elo0: f64,
+    pub elo1: f64,
+    pub alpha: f64,
+    pub beta: f64
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SprtResult {
+    Continue,
+    AcceptH0,
+    AcceptH1
+}
+
+//results from the perspective of the first engine
+#[derive(Clone, Copy, Default)]
+pub struct MatchStats {
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32
+}
+
+impl MatchStats {
+    pub fn new() -> MatchStats {
+        MatchStats { wins: 0, losses: 0, draws: 0 }
+    }
+
+    pub fn get_games(&self) -> u32 {
+        self.wins + self.losses + self.draws
+    }
+
+    pub fn get_score(&self) -> f64 {
+        let games = self.get_games();
+        if games == 0 {
+            return 0.5;
+        }
+        (self.wins as f64 + 0.5 * self.draws as f64) / games as f64
+    }
+
+    //variance of a single game result
+    fn get_variance(&self) -> f64 {
+        let games = self.get_games() as f64;
+        if games == 0.0 {
+            return 0.0;
+        }
+        let score = self.get_score();
+        (self.wins as f64 * (1.0 - score).powi(2) +
+            self.losses as f64 * score.powi(2) +
+            self.draws as f64 * (0.5 - score).powi(2)) / games
+    }
+
+    pub fn get_elo(&self) -> f64 {
+        score_to_elo(self.get_score())
+    }
+
+    //half width of the 95% confidence interval
+    pub fn get_elo_error(&self) -> f64 {
+        let games = self.get_games() as f64;
+        if games == 0.0 {
+            return 0.0;
+        }
+        let score = self.get_score();
+        let deviation = 1.959964 * (self.get_variance() / games).sqrt();
+        let error = (score_to_elo(score + deviation) - score_to_elo(score - deviation)) / 2.0;
+        if error.is_nan() {
+            //all games won or all lost
+            return f64::INFINITY;
+        }
+        error
+    }
+
+    //log likelihood ratio of H1 (elo1) against H0 (elo0), normal approximation
+    pub fn get_llr(&self, sprt: &Sprt) -> f64 {
+        let games = self.get_games() as f64;
+        let variance = self.get_variance();
+        if games == 0.0 || variance <= 0.0 {
+            return 0.0;
+        }
+
+        let score = self.get_score();
+        let score0 = elo_to_score(sprt.elo0);
+        let score1 = elo_to_score(sprt.elo1);
+
+        0.5 * games * (score1 - score0) * (2.0 * score - score0 - score1) / variance
+    }
+
+    pub fn get_sprt_result(&self, sprt: &Sprt) -> SprtResult {
+        let llr = self.get_llr(sprt);
+        let (lower, upper) = sprt.get_bounds();
+        if llr <= lower {
+            SprtResult::AcceptH0
+        } else if llr >= upper {
+            SprtResult::AcceptH1
+        } else {
+            SprtResult::Continue
+        }
+    }
+}
+
+impl Sprt {
+    pub fn get_bounds(&self) -> (f64, f64) {
+        (
+            (self.beta / (1.0 - self.alpha)).ln(),
+            ((1.0 - self.beta) / self.alpha).ln()
+        )
+    }
+}
+
+pub fn elo_to_score(elo: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-elo / 400.0))
+}
+
+pub fn score_to_elo(score: f64) -> f64 {
+    if score <= 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if score >= 1.0 {
+        return f64::INFINITY;
+    }
+    -400.0 * (1.0 / score - 1.0).log10()
+}
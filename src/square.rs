@@ -1,3 +1,5 @@
+use crate::error::HaddockError;
+
 const CHAR_BASE: u8 = 97;
 const MASK: u8 = 0b0000_0111;
 
@@ -149,9 +151,10 @@ impl Square {
         Square((y << 3) | x)
     }
 
-    pub fn from_str(value: &str) -> Option<Square> {
+    pub fn from_str(value: &str) -> Result<Square, HaddockError> {
+        let invalid = || HaddockError::InvalidSquare(value.to_string());
         if value.len() != 2 {
-            return None;
+            return Err(invalid());
         }
         
         let mut x: u8;
@@ -162,10 +165,10 @@ impl Square {
                     x -= CHAR_BASE;
                 }
                 else {
-                    return None;
+                    return Err(invalid());
                 }
             },
-            None => return None
+            None => return Err(invalid())
         }
 
         let y: u8;
@@ -177,17 +180,17 @@ impl Square {
                             y = (d - 1) as u8;
                         }
                         else {
-                            return None;
+                            return Err(invalid());
                         }
 
                     },
-                    None => return None
+                    None => return Err(invalid())
                 }
             },
-            None => return None
+            None => return Err(invalid())
         }
 
-        Some(Square::from_xy(x, y))
+        Ok(Square::from_xy(x, y))
     }
 
     pub fn to_fen(self) -> String {
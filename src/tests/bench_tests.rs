@@ -0,0 +1,18 @@
+#[test]
+fn test_bench_signature_deterministic() {
+    //act
+    let logger = crate::logger::Logger::new(Box::new(std::io::sink()));
+    let result_1 = crate::bench::run(2, &logger);
+    let result_2 = crate::bench::run(2, &logger);
+
+    //assert
+    assert!(result_1.nodes > 0, "bench searched no nodes");
+    assert_eq!(result_1.get_signature(), result_2.get_signature(), "bench signature not deterministic");
+}
+
+#[test]
+fn test_bench_get_depth() {
+    assert_eq!(Some(crate::bench::DEFAULT_BENCH_DEPTH), crate::bench::get_depth(""));
+    assert_eq!(Some(7), crate::bench::get_depth("7"));
+    assert_eq!(None, crate::bench::get_depth("x"));
+}
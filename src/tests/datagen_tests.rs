@@ -0,0 +1,82 @@
+fn args(text: &str) -> Vec<String> {
+    text.split_whitespace().map(|s| s.to_string()).collect()
+}
+
+fn create_searcher() -> crate::searcher::Searcher {
+    let (_sender, receiver) = std::sync::mpsc::channel();
+    let position = crate::parser::parse_startpos().unwrap();
+    crate::searcher::Searcher::new(receiver, position, std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)), crate::hash_counter::HashCounter::new())
+}
+
+#[test]
+fn test_parse_args() {
+    //act
+    let config = crate::datagen::parse_args(&args("-output data.epd -games 10 -nodes 5000 -randomplies 4 -maxplies 100 -maxscore 800 -seed 3")).unwrap();
+
+    //assert
+    assert_eq!("data.epd", config.output);
+    assert_eq!(10, config.games);
+    assert!(match config.search_type { crate::searchtype::SearchType::Nodes(5000) => true, _ => false });
+    assert_eq!(4, config.random_plies);
+    assert_eq!(100, config.max_plies);
+    assert_eq!(800, config.max_score);
+    assert_eq!(3, config.seed);
+    assert!(crate::datagen::parse_args(&args("-games")).is_err(), "Missing value accepted");
+    assert!(crate::datagen::parse_args(&args("-depth x")).is_err(), "Invalid value accepted");
+    assert!(crate::datagen::parse_args(&args("-colour white")).is_err(), "Unknown option accepted");
+}
+
+#[test]
+fn test_to_epd() {
+    //arrange
+    let entry = crate::datagen::SfenEntry {
+        position: crate::tests::position_from_fen("4k3/8/8/8/8/8/4P3/4K3 b - - 3 40"),
+        score: -120,
+        result: crate::outcome::Outcome::BlackIsMate(60)
+    };
+
+    //act
+    let epd = entry.to_epd();
+
+    //assert
+    assert_eq!("4k3/8/8/8/8/8/4P3/4K3 b - - ce -120; c9 \"1-0\";", epd);
+    let labelled = crate::tuner::parse_labelled_epd(&epd).unwrap();
+    assert_eq!(1.0, labelled[0].1);
+}
+
+#[test]
+fn test_random_opening_seeded() {
+    //arrange
+    let mut rng_1 = <rand::rngs::StdRng as rand::SeedableRng>::seed_from_u64(42);
+    let mut rng_2 = <rand::rngs::StdRng as rand::SeedableRng>::seed_from_u64(42);
+
+    //act
+    let record_1 = crate::datagen::get_random_opening(&mut rng_1, 6).unwrap();
+    let record_2 = crate::datagen::get_random_opening(&mut rng_2, 6).unwrap();
+
+    //assert
+    assert_eq!(6, record_1.get_moves().len());
+    assert_eq!(record_1.get_position().get_hash(), record_2.get_position().get_hash(), "Opening not determined by the seed");
+}
+
+#[test]
+fn test_play_game() {
+    //arrange
+    let mut searcher = create_searcher();
+    let mut config = crate::datagen::parse_args(&args("-depth 2 -maxplies 30 -seed 1")).unwrap();
+    config.max_score = 10000;
+    let mut rng = <rand::rngs::StdRng as rand::SeedableRng>::seed_from_u64(config.seed);
+    let record = crate::datagen::get_random_opening(&mut rng, 4).unwrap();
+
+    //act
+    let (result, entries) = crate::datagen::play_game(&mut searcher, record, &config);
+
+    //assert
+    assert!(!entries.is_empty(), "No positions recorded");
+    assert!(entries.len() <= 26);
+    for entry in entries.iter() {
+        let position = &entry.position;
+        assert!(!crate::generator::Generator::new(position).is_check(position.get_active_color()), "Position in check recorded");
+        assert_eq!(result.score(), entry.result.score());
+    }
+}
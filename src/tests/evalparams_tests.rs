@@ -0,0 +1,110 @@
+#[test]
+fn test_text_roundtrip() {
+    //arrange
+    let mut params = crate::evalparams::EvalParams::default();
+    params.bishop_pair = crate::score::Score::new(11, -12);
+    params.piece_square[3][63] = crate::score::Score::new(-7, 8);
+
+    //act
+    let parsed = crate::evalparams::EvalParams::parse(&params.to_text());
+
+    //assert
+    assert_eq!(Ok(params), parsed);
+}
+
+#[test]
+fn test_json_roundtrip() {
+    //arrange
+    let mut params = crate::evalparams::EvalParams::default();
+    params.king_attack_weight[2] = 9;
+    params.passed_pawn[6] = crate::score::Score::new(70, 130);
+
+    //act
+    let parsed = crate::evalparams::EvalParams::parse(&params.to_json());
+
+    //assert
+    assert_eq!(Ok(params), parsed);
+}
+
+#[test]
+fn test_values_roundtrip() {
+    //arrange
+    let params = crate::evalparams::EvalParams::default();
+    let mut values = params.to_values();
+    values[0] += 10;
+
+    //act
+    let mut changed = params.clone();
+    changed.set_values(&values);
+
+    //assert
+    assert_eq!(crate::score::Score::new(110, 100), changed.piece_value[0]);
+    assert_eq!(values, changed.to_values());
+}
+
+#[test]
+fn test_parse_partial() {
+    //act
+    let text = crate::evalparams::EvalParams::parse("# only the pair\nbishop_pair 40 60\n");
+    let json = crate::evalparams::EvalParams::parse("{ \"piece_value\": [[90, 110], [0, 0], [900, 900], [500, 500], [300, 300], [300, 300]] }");
+
+    //assert
+    let text = text.unwrap();
+    assert_eq!(crate::score::Score::new(40, 60), text.bishop_pair);
+    assert_eq!(crate::evalparams::DEFAULT_PARAMS.piece_value, text.piece_value);
+    assert_eq!(crate::score::Score::new(90, 110), json.unwrap().piece_value[0]);
+}
+
+#[test]
+fn test_parse_errors() {
+    //act & assert
+    assert_eq!(
+        Err(crate::error::HaddockError::UnknownEvalParam("queen_pair".to_string())),
+        crate::evalparams::EvalParams::parse("queen_pair 1 2")
+    );
+    assert_eq!(
+        Err(crate::error::HaddockError::EvalParamCount("bishop_pair".to_string(), 2, 3)),
+        crate::evalparams::EvalParams::parse("bishop_pair 1 2 3")
+    );
+    assert_eq!(
+        Err(crate::error::HaddockError::InvalidEvalParamValue("x".to_string())),
+        crate::evalparams::EvalParams::parse("bishop_pair 1 x")
+    );
+    assert!(crate::evalparams::EvalParams::parse("{ \"bishop_pair\": [1, 2]").is_err(), "Unterminated json accepted");
+    assert!(crate::evalparams::EvalParams::parse("{ \"bishop_pair\" [1, 2] }").is_err(), "Missing colon accepted");
+}
+
+#[test]
+fn test_params_change_evaluation() {
+    //arrange
+    let position = crate::tests::position_from_fen("4k3/8/8/8/8/8/8/2B1KB2 w - - 0 1");
+    let mut params = crate::evalparams::EvalParams::default();
+    params.bishop_pair = params.bishop_pair + crate::score::Score::new(100, 100);
+
+    //act
+    let default_value = crate::evaluation::evaluate(&position, 0).score();
+    let changed_value = crate::evaluation::evaluate_with_params(&position, 0, &params).score();
+    let mut evaluator = crate::evaluation::Evaluator::new();
+    evaluator.set_params(params);
+
+    //assert
+    assert_eq!(default_value + 100, changed_value);
+    assert_eq!(changed_value, evaluator.evaluate(&position, 0).score());
+}
+
+#[test]
+fn test_params_for_phase_mobility_and_king_safety() {
+    //arrange
+    let position = crate::tests::position_from_fen("r1bq1rk1/ppp2ppp/2n5/3Qp1N1/2B1n3/8/PPP2PPP/RNB1K2R w KQ - 0 1");
+    let params = crate::evalparams::EvalParams::parse("knight_mobility_base 5\nphase_weight 0 0 0 0 0 0\nking_danger_divisor 0\nking_safety_material 0 0 0 0 0 0").unwrap();
+
+    //act
+    let default_value = crate::evaluation::evaluate(&position, 0).score();
+    let changed_value = crate::evaluation::evaluate_with_params(&position, 0, &params).score();
+
+    //assert
+    //weights set to 0 are no divisors
+    assert_eq!(5, params.knight_mobility_base);
+    assert_eq!(0, crate::evaluation::get_phase(&position, &params));
+    assert_ne!(default_value, changed_value);
+}
@@ -142,4 +142,67 @@ fn test_fools_mate() {
         position
     );
 }
-*/
\ No newline at end of file
+*/
+
+#[test]
+fn test_phase() {
+    //arrange
+    let startpos = crate::parser::parse_startpos().unwrap();
+    let without_queens = crate::tests::position_from_fen("rnb1kbnr/pppppppp/8/8/8/8/PPPPPPPP/RNB1KBNR w KQkq - 0 1");
+    let pawn_ending = crate::tests::position_from_fen("4k3/pppppppp/8/8/8/8/PPPPPPPP/4K3 w - - 0 1");
+    let promoted = crate::tests::position_from_fen("qqqqkqqq/8/8/8/8/8/8/QQQQKQQQ w - - 0 1");
+
+    //act & assert
+    assert_eq!(crate::score::PHASE_MAX, crate::evaluation::get_phase(&startpos, &crate::evalparams::DEFAULT_PARAMS));
+    assert_eq!(16 * crate::score::PHASE_MAX / 24, crate::evaluation::get_phase(&without_queens, &crate::evalparams::DEFAULT_PARAMS));
+    assert_eq!(0, crate::evaluation::get_phase(&pawn_ending, &crate::evalparams::DEFAULT_PARAMS));
+    assert_eq!(crate::score::PHASE_MAX, crate::evaluation::get_phase(&promoted, &crate::evalparams::DEFAULT_PARAMS));
+}
+
+#[test]
+fn test_evaluation_startpos_equal() {
+    //arrange
+    let startpos = crate::parser::parse_startpos().unwrap();
+
+    //act
+    let outcome = crate::evaluation::evaluate(&startpos, 0);
+
+    //assert
+    assert_eq!(crate::outcome::Outcome::Undecided(0, 0), outcome);
+}
+
+#[test]
+fn test_evaluation_mirrored() {
+    let mut evaluator = crate::evaluation::Evaluator::new();
+    for fen in crate::bench::BENCH_FENS.iter() {
+        //arrange
+        let position = crate::tests::position_from_fen(fen);
+        let mirrored = position.get_mirrored();
+
+        //act
+        let value = crate::evaluation::evaluate(&position, 0).score();
+        let mirrored_value = crate::evaluation::evaluate(&mirrored, 0).score();
+        let cached_value = evaluator.evaluate(&mirrored, 0).score();
+
+        //assert
+        assert_eq!(-value, mirrored_value, "Evaluation not mirrored for {}", fen);
+        assert_eq!(mirrored_value, cached_value, "Cached evaluation differs for {}", fen);
+    }
+}
+
+#[test]
+fn test_trace() {
+    //arrange
+    let position = crate::tests::position_from_fen("r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3");
+    let value = crate::evaluation::evaluate(&position, 0).score();
+
+    //act
+    let trace = crate::evaluation::trace(&position, &crate::evalparams::DEFAULT_PARAMS);
+
+    //assert
+    for term in ["Material", "Piece squares", "Pawn structure", "Free passers", "King safety", "Pieces", "Total"].iter() {
+        assert!(trace.contains(term), "Term {} missing in\n{}", term, trace);
+    }
+    assert!(trace.contains("Phase: 256 of 256"), "Phase missing in\n{}", trace);
+    assert!(trace.contains(&format!("Evaluation: {} (white side)", value)), "Evaluation missing in\n{}", trace);
+}
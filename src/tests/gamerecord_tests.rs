@@ -0,0 +1,77 @@
+fn create_record(fen: &str, moves: &str) -> crate::gamerecord::GameRecord {
+    let mut record = crate::gamerecord::GameRecord::new(crate::tests::position_from_fen(fen));
+    for m in moves.split_whitespace() {
+        let mv = record.get_position().analyze_move(crate::move_::Move_::from_str(m).unwrap());
+        record.apply_move(mv);
+    }
+    record
+}
+
+#[test]
+fn test_status_mate_and_stalemate() {
+    //act
+    let fools_mate = create_record(crate::global::FEN_STARTPOS, "f2f3 e7e5 g2g4 d8h4");
+    let stalemate = create_record("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1", "");
+
+    //assert
+    assert_eq!(crate::gamerecord::GameStatus::Checkmate(crate::global::COLOR_WHITE), fools_mate.get_status());
+    assert_eq!(4, fools_mate.get_moves().len());
+    assert_eq!(5, fools_mate.get_positions().len());
+    assert_eq!(crate::gamerecord::GameStatus::Stalemate, stalemate.get_status());
+}
+
+#[test]
+fn test_status_repetitions() {
+    //arrange
+    let shuffle = "g1f3 g8f6 f3g1 f6g8";
+
+    //act
+    let once = create_record(crate::global::FEN_STARTPOS, shuffle);
+    let twice = create_record(crate::global::FEN_STARTPOS, &[shuffle; 2].join(" "));
+    let four_times = create_record(crate::global::FEN_STARTPOS, &[shuffle; 4].join(" "));
+
+    //assert
+    assert_eq!(2, once.get_repetition_count());
+    assert_eq!(crate::gamerecord::GameStatus::Ongoing, once.get_status());
+    assert_eq!(crate::gamerecord::GameStatus::ThreefoldRepetition, twice.get_status());
+    assert_eq!(crate::gamerecord::GameStatus::FivefoldRepetition, four_times.get_status());
+}
+
+#[test]
+fn test_status_halfmove_clock() {
+    //act
+    let fifty = create_record("4k3/8/8/8/8/8/4P3/R3K3 w - - 99 80", "a1a2");
+    let seventy_five = create_record("4k3/8/8/8/8/8/4P3/R3K3 w - - 149 100", "a1a2");
+    let reset = create_record("4k3/8/8/8/8/8/4P3/R3K3 w - - 99 80", "e2e4");
+
+    //assert
+    assert_eq!(crate::gamerecord::GameStatus::FiftyMoveRule, fifty.get_status());
+    assert_eq!(crate::gamerecord::GameStatus::SeventyFiveMoveRule, seventy_five.get_status());
+    assert_eq!(crate::gamerecord::GameStatus::Ongoing, reset.get_status());
+}
+
+#[test]
+fn test_status_mate_before_halfmove_clock() {
+    //act
+    let record = create_record("7k/8/6K1/8/8/8/8/R7 w - - 149 100", "a1a8");
+
+    //assert
+    assert_eq!(crate::gamerecord::GameStatus::Checkmate(crate::global::COLOR_BLACK), record.get_status());
+}
+
+#[test]
+fn test_status_material() {
+    let cases = [
+        ("4k3/8/8/8/8/8/8/4K3 w - - 0 1", crate::gamerecord::GameStatus::InsufficientMaterial),
+        ("4k3/8/8/8/8/8/8/4KN2 w - - 0 1", crate::gamerecord::GameStatus::InsufficientMaterial),
+        ("4kb2/8/8/8/8/8/8/2B1K3 w - - 0 1", crate::gamerecord::GameStatus::DeadPosition),
+        ("4k3/8/8/8/8/8/8/2B1KB2 w - - 0 1", crate::gamerecord::GameStatus::Ongoing),
+        ("4k3/8/8/8/8/8/8/3NKN2 w - - 0 1", crate::gamerecord::GameStatus::Ongoing),
+        ("4k3/8/8/8/8/8/8/3BKN2 w - - 0 1", crate::gamerecord::GameStatus::Ongoing),
+        ("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1", crate::gamerecord::GameStatus::Ongoing)
+    ];
+
+    for (fen, expected) in cases.iter() {
+        assert_eq!(*expected, create_record(fen, "").get_status(), "Unexpected status for '{}'", fen);
+    }
+}
@@ -692,6 +692,24 @@ fn test_king_castling_no_status() {
 
 }
 
+#[test]
+fn test_attack_board_stops_at_any_piece() {
+    //arrange
+    //white rook a1, own pawn a3, black knight c1
+    let position = crate::tests::position_from_fen("4k3/8/8/8/8/P7/8/R1n1K3 w - - 0 1");
+
+    let mut expected = crate::bitboard::BitBoard::new();
+    for s in ["a2", "a3", "b1", "c1"].iter() {
+        expected.set(sq(s));
+    }
+
+    //act
+    let attacks = Generator::new(&position).get_attack_board(sq("a1"), crate::piecetype::PieceType::new_rook(crate::global::COLOR_WHITE));
+
+    //assert
+    assert_eq!(expected, attacks, "Rook attacks not as expected");
+}
+
 /*
 
 #[test]
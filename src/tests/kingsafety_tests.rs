@@ -0,0 +1,67 @@
+fn king_safety(fen: &str) -> crate::score::Score {
+    crate::kingsafety::evaluate_king_safety(&crate::tests::position_from_fen(fen), &crate::evalparams::DEFAULT_PARAMS)
+}
+
+#[test]
+fn test_king_safety_startpos_equal() {
+    assert_eq!(crate::score::Score::default(), king_safety(crate::global::FEN_STARTPOS));
+}
+
+#[test]
+fn test_pawn_shield() {
+    //arrange
+    let shield = "r4rk1/ppp2ppp/8/8/8/8/PPP2PPP/R4RK1 w - - 0 1";
+    let advanced = "r4rk1/ppp2ppp/8/8/8/6PP/PPP2P2/R4RK1 w - - 0 1";
+    let open = "r4rk1/ppp2ppp/8/8/8/8/PPP2P2/R4RK1 w - - 0 1";
+
+    //act & assert
+    assert!(king_safety(advanced).mg < king_safety(shield).mg, "Advanced shield not worse");
+    assert!(king_safety(open).mg < king_safety(advanced).mg, "Missing shield not worse");
+}
+
+#[test]
+fn test_pawn_storm() {
+    //arrange
+    let no_storm = "r4rk1/ppp2ppp/8/8/8/8/PPP2PPP/R4RK1 w - - 0 1";
+    let storm = "r4rk1/ppp2ppp/8/8/8/6p1/PPP2PPP/R4RK1 w - - 0 1";
+
+    //act & assert
+    assert!(king_safety(storm).mg < king_safety(no_storm).mg, "Pawn storm not penalized");
+}
+
+#[test]
+fn test_king_attack() {
+    //arrange
+    //queen and knight both hit the squares around the white king
+    let attacked = "6k1/5ppp/8/8/8/5n2/5PPP/3q1RK1 w - - 0 1";
+    let single_attacker = "6k1/5ppp/8/8/8/8/5PPP/3q1RK1 w - - 0 1";
+
+    //act & assert
+    assert!(king_safety(attacked).mg < king_safety(single_attacker).mg, "King attack not penalized");
+}
+
+#[test]
+fn test_king_safety_scaled_by_material() {
+    //arrange
+    //white king without shelter, black loses all its pieces
+    let full_material = "rnbqkbnr/pppppppp/8/8/8/8/PPPPP3/RNBQ1RK1 w kq - 0 1";
+    let less_material = "4k3/pppppppp/8/8/8/8/PPPPP3/RNBQ1RK1 w - - 0 1";
+
+    //act & assert
+    assert!(king_safety(full_material).mg < king_safety(less_material).mg, "King safety not scaled");
+}
+
+#[test]
+fn test_king_safety_mirrored() {
+    for fen in crate::bench::BENCH_FENS.iter() {
+        //arrange
+        let position = crate::tests::position_from_fen(fen);
+
+        //act & assert
+        assert_eq!(
+            -crate::kingsafety::evaluate_king_safety(&position, &crate::evalparams::DEFAULT_PARAMS),
+            crate::kingsafety::evaluate_king_safety(&position.get_mirrored(), &crate::evalparams::DEFAULT_PARAMS),
+            "King safety not mirrored for {}", fen
+        );
+    }
+}
@@ -0,0 +1,13 @@
+#[test]
+fn test_format_log_line() {
+    //arrange
+    let time = std::time::UNIX_EPOCH + std::time::Duration::from_millis(((13 * 60 + 45) * 60 + 12) * 1000 + 345);
+
+    //act
+    let input = crate::logger::format_log_line(time, crate::logger::DIRECTION_INPUT, "go depth 5");
+    let output = crate::logger::format_log_line(time, crate::logger::DIRECTION_OUTPUT, "bestmove e2e4");
+
+    //assert
+    assert_eq!("13:45:12.345 >> go depth 5", input);
+    assert_eq!("13:45:12.345 << bestmove e2e4", output);
+}
@@ -0,0 +1,69 @@
+fn args(line: &str) -> Vec<String> {
+    line.split_whitespace().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn test_parse_match_args() {
+    //act
+    let config = crate::matchrunner::parse_args(&args("-engine cmd=internal name=a -engine cmd=internal -each depth=2 -games 10 -sprt elo0=-5 elo1=5")).unwrap();
+
+    //assert
+    assert_eq!(2, config.engines.len(), "Unexpected number of engines");
+    assert_eq!(Some("a".to_string()), config.engines[0].name);
+    assert_eq!("depth 2", config.engines[1].search_type.to_go_args());
+    assert_eq!(10, config.games);
+    assert_eq!(-5.0, config.sprt.unwrap().elo0);
+    assert_eq!(Err(crate::error::HaddockError::InvalidMatchArgs("at least two engines are needed".to_string())), crate::matchrunner::parse_args(&args("-engine cmd=internal")).map(|c| c.games));
+    assert_eq!(3, crate::matchrunner::parse_args(&args("-engine -engine -engine")).unwrap().engines.len(), "gauntlet not parsed");
+}
+
+#[test]
+fn test_play_game_mate() {
+    //arrange
+    //white mates in one with Ra8
+    let opening = crate::pgn::Opening { fen: "7k/8/6K1/8/8/8/8/R7 w - - 0 1".to_string(), moves: Vec::new() };
+    let mut white = crate::player::SearcherPlayer::new("white", crate::searchtype::SearchType::Depth(2));
+    let mut black = crate::player::SearcherPlayer::new("black", crate::searchtype::SearchType::Depth(2));
+
+    //act
+    let result = crate::matchrunner::play_game(&mut white, &mut black, &opening, 0).unwrap();
+
+    //assert
+    assert_eq!("1-0", result.to_pgn_result(), "Unexpected result");
+    assert_eq!("checkmate", result.termination);
+    assert_eq!(1, result.moves.len());
+}
+
+#[test]
+fn test_play_game_insufficient_material() {
+    //arrange
+    let opening = crate::pgn::Opening { fen: "7k/8/6K1/8/8/8/8/N7 w - - 0 1".to_string(), moves: Vec::new() };
+    let mut white = crate::player::SearcherPlayer::new("white", crate::searchtype::SearchType::Depth(1));
+    let mut black = crate::player::SearcherPlayer::new("black", crate::searchtype::SearchType::Depth(1));
+
+    //act
+    let result = crate::matchrunner::play_game(&mut white, &mut black, &opening, 0).unwrap();
+
+    //assert
+    assert_eq!("1/2-1/2", result.to_pgn_result(), "Unexpected result");
+    assert_eq!("insufficient material", result.termination);
+}
+
+#[test]
+fn test_internal_engine_options() {
+    //arrange
+    let depth = crate::searchtype::SearchType::Depth(1);
+    let options = |list: &[(&str, &str)]| list.iter().map(|(n, v)| (n.to_string(), v.to_string())).collect::<Vec<(String, String)>>();
+
+    //act
+    let contempt = crate::player::SearcherPlayer::with_options("a", depth, &options(&[("Contempt", "20")])).map(|_| ());
+    let unknown = crate::player::SearcherPlayer::with_options("a", depth, &options(&[("Hash", "64")])).map(|_| ());
+    let no_network = crate::player::SearcherPlayer::with_options("a", depth, &options(&[("Use NNUE", "true")])).map(|_| ());
+    let no_eval_file = crate::player::SearcherPlayer::with_options("a", depth, &options(&[("EvalFile", "missing.txt")])).map(|_| ());
+
+    //assert
+    assert_eq!(Ok(()), contempt);
+    assert_eq!(Err(crate::error::HaddockError::UnsupportedEngineOption("a".to_string(), "Hash".to_string())), unknown);
+    assert_eq!(Err(crate::error::HaddockError::MissingEngineOption("Use NNUE".to_string(), "NNUE File".to_string())), no_network);
+    assert!(no_eval_file.is_err(), "Missing evaluation file accepted");
+}
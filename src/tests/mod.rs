@@ -4,4 +4,31 @@ mod square_tests;
 mod move_tests;
 mod position_tests;
 mod evaluation_tests;
-mod zobrist_tests;
\ No newline at end of file
+mod zobrist_tests;
+mod bench_tests;
+mod san_tests;
+mod pgn_tests;
+mod sprt_tests;
+mod matchrunner_tests;
+mod uciclient_tests;
+mod reporter_tests;
+mod logger_tests;
+mod uci_tests;
+mod searcher_tests;
+mod positioncommand_tests;
+mod validation_tests;
+mod gamerecord_tests;
+mod pawnstructure_tests;
+mod kingsafety_tests;
+mod pieces_tests;
+mod evalparams_tests;
+mod tuner_tests;
+mod datagen_tests;
+mod nnue_tests;
+mod trainer_tests;
+
+//position of a fen that is known to be valid
+pub fn position_from_fen(fen: &str) -> crate::position::Position {
+    let fen_parts = fen.split(" ").collect::<Vec<&str>>();
+    crate::parser::parse_fen(&fen_parts).unwrap()
+}
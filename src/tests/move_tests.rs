@@ -34,4 +34,17 @@ pub fn test_from_to_str_promo() {
 
     //assert
     assert_eq!(mv_string, mv_str_back, "Unexpected move from string");
-}
\ No newline at end of file
+}
+
+#[test]
+pub fn test_from_str_errors() {
+    //act
+    let too_short = crate::move_::Move_::from_str("e2e");
+    let bad_square = crate::move_::Move_::from_str("e2i4");
+    let bad_promo = crate::move_::Move_::from_str("e7e8x");
+
+    //assert
+    assert_eq!(Err(crate::error::HaddockError::InvalidMove("e2e".to_string())), too_short);
+    assert_eq!(Err(crate::error::HaddockError::InvalidMove("e2i4".to_string())), bad_square);
+    assert_eq!(Err(crate::error::HaddockError::InvalidPromotionPiece('x')), bad_promo);
+}
@@ -0,0 +1,136 @@
+//small random weights, the accumulator can not overflow with them
+fn random_network(seed: u64) -> crate::nnue::Network {
+    let mut rng = <rand::rngs::StdRng as rand::SeedableRng>::seed_from_u64(seed);
+    let mut network = crate::nnue::Network::new();
+    for w in network.feature_weights.iter_mut().chain(network.feature_bias.iter_mut()) {
+        *w = rand::Rng::gen_range(&mut rng, -20, 21);
+    }
+    for w in network.output_weights.iter_mut() {
+        *w = rand::Rng::gen_range(&mut rng, -64, 65);
+    }
+    network.output_bias = 1000;
+    network
+}
+
+fn play(position: &mut crate::position::Position, mv_str: &str) {
+    let mv = crate::generator::Generator::new(position).generate_legal_moves().into_iter()
+        .find(|m| m.to_fen() == mv_str)
+        .expect("Move not legal");
+    position.apply_move(mv);
+}
+
+#[test]
+fn test_incremental_accumulator() {
+    //arrange
+    let network = random_network(1);
+    let mut position = crate::tests::position_from_fen("r3k2r/1P6/8/3pP3/8/8/6p1/R3K2R w KQkq d6 0 1");
+    let mut accumulator = network.get_accumulator(&position);
+
+    //act, assert
+    //en passant, castling, promotion with capture, promotion, castling
+    for mv_str in ["e5d6", "e8g8", "b7a8q", "g2g1n", "e1c1"].iter() {
+        let parent = position;
+        play(&mut position, mv_str);
+        network.update_accumulator(&mut accumulator, &parent, &position);
+        assert_eq!(network.get_accumulator(&position), accumulator, "Accumulator differs after {}", mv_str);
+    }
+}
+
+#[test]
+fn test_network_file() {
+    //arrange
+    let network = random_network(2);
+    let path = std::env::temp_dir().join(format!("haddock_nnue_{}.bin", std::process::id()));
+    let path = path.to_str().unwrap();
+
+    //act
+    network.save(path).unwrap();
+    let loaded = crate::nnue::Network::load(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+
+    //assert
+    assert_eq!(network.to_bytes(), loaded.to_bytes());
+    let bytes = network.to_bytes();
+    assert!(crate::nnue::Network::from_bytes(&bytes[..bytes.len() - 1]).is_err(), "Truncated file accepted");
+    assert!(crate::nnue::Network::from_bytes(b"HADDOCK").is_err(), "Other file accepted");
+    let mut other_size = bytes.clone();
+    other_size[12] = 64;
+    assert!(crate::nnue::Network::from_bytes(&other_size).is_err(), "Other hidden size accepted");
+    let mut large_weight = network;
+    large_weight.feature_weights[5] = crate::nnue::MAX_FEATURE_WEIGHT + 1;
+    assert!(crate::nnue::Network::from_bytes(&large_weight.to_bytes()).is_err(), "Feature weight above the limit accepted");
+}
+
+#[test]
+fn test_network_evaluation_large_weights() {
+    //arrange
+    let mut network = crate::nnue::Network::new();
+    for w in network.feature_bias.iter_mut() {
+        *w = crate::nnue::MAX_FEATURE_WEIGHT;
+    }
+    for w in network.output_weights.iter_mut() {
+        *w = i16::MAX;
+    }
+    network.output_bias = i32::MAX;
+    let position = crate::tests::position_from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1");
+
+    //act
+    let value = network.evaluate(&network.get_accumulator(&position), position.get_active_color());
+
+    //assert
+    assert!(value > 0 && value < crate::outcome::MATE_AGAINST_BLACK, "Evaluation overflowed: {}", value);
+}
+
+#[test]
+fn test_network_evaluation_mirrored() {
+    let network = random_network(3);
+    for fen in crate::bench::BENCH_FENS.iter() {
+        //arrange
+        let position = crate::tests::position_from_fen(fen);
+        let mirrored = position.get_mirrored();
+
+        //act
+        let value = crate::evaluation::evaluate_network(&position, 0, &network, &network.get_accumulator(&position)).score();
+        let mirrored_value = crate::evaluation::evaluate_network(&mirrored, 0, &network, &network.get_accumulator(&mirrored)).score();
+
+        //assert
+        assert_eq!(-value, mirrored_value, "Evaluation not mirrored for {}", fen);
+    }
+}
+
+#[test]
+fn test_searcher_with_network() {
+    //arrange
+    let (_sender, receiver) = std::sync::mpsc::channel();
+    //no captures, the quiescence search returns the evaluation
+    let position = crate::tests::position_from_fen("4k3/pppppppp/8/8/8/8/PPPPPPPP/3QK3 w - - 0 1");
+    let mut searcher = crate::searcher::Searcher::new(receiver, position, std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)), crate::hash_counter::HashCounter::new());
+    let value_hand_crafted = searcher.quiesce().unwrap().0.score();
+
+    //act
+    searcher.set_network(Some(std::sync::Arc::new(crate::nnue::Network::new())));
+    let value_network = searcher.quiesce().unwrap().0.score();
+    searcher.set_network(None);
+    let value_detached = searcher.quiesce().unwrap().0.score();
+
+    //assert
+    //all weights zero
+    assert_eq!(0, value_network);
+    assert!(value_hand_crafted > 500);
+    assert_eq!(value_hand_crafted, value_detached);
+}
+
+#[test]
+fn test_search_with_network() {
+    //arrange
+    let (_sender, receiver) = std::sync::mpsc::channel();
+    let position = crate::tests::position_from_fen("4k3/8/8/3q4/4P3/8/8/4K3 w - - 0 1");
+    let mut searcher = crate::searcher::Searcher::new(receiver, position, std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)), crate::hash_counter::HashCounter::new());
+    searcher.set_network(Some(std::sync::Arc::new(random_network(4))));
+
+    //act
+    let result = searcher.find_best_move(crate::searchtype::SearchType::Depth(3));
+
+    //assert
+    assert!(result.is_ok());
+}
@@ -23,3 +23,28 @@ fn test_parse_startpos() {
     assert_eq!(crate::global::FEN_STARTPOS, actual_fen, "position not as expected");
 }
 
+
+#[test]
+fn test_parse_fen_errors() {
+    use crate::error::HaddockError;
+
+    let cases = [
+        ("8/8/8/8/8/8/8/8", HaddockError::FenTooShort),
+        ("8/8/8/8/8/8/8/8 w - - 0 1 x", HaddockError::FenTooManyFields(7)),
+        ("8/8/8/8/8/8/8 w - - 0 1", HaddockError::BadRankCount(7)),
+        ("8/8/8/8/8/8/8/7 w - - 0 1", HaddockError::BadRankLength(1, 7)),
+        ("8/8/8/8/8/8/8/ppppppppp w - - 0 1", HaddockError::BadRankLength(1, 9)),
+        ("4k3/8/8/8/8/8/8/4KX2 w - - 0 1", HaddockError::InvalidPieceChar('X')),
+        ("4k3/8/8/8/8/8/8/4K3 - - - 0 1", HaddockError::InvalidActiveColor("-".to_string())),
+        ("4k3/8/8/8/8/8/8/4K3 w KK - 0 1", HaddockError::IllegalCastlingField("KK".to_string())),
+        ("4k3/8/8/8/8/8/8/4K3 w Kx - 0 1", HaddockError::IllegalCastlingField("Kx".to_string())),
+        ("4k3/8/8/8/8/8/8/4K3 w - e9 0 1", HaddockError::InvalidEnPassantSquare("e9".to_string())),
+        ("4k3/8/8/8/8/8/8/4K3 w - - x 1", HaddockError::InvalidHalfMoveClock("x".to_string())),
+        ("4k3/8/8/8/8/8/8/4K3 w - - 0 -1", HaddockError::InvalidFullMoveNumber("-1".to_string()))
+    ];
+
+    for (fen, expected) in cases.iter() {
+        let fen_parts = fen.split(" ").collect::<Vec<&str>>();
+        assert_eq!(Some(expected.clone()), crate::parser::parse_fen(&fen_parts).err(), "Unexpected result for '{}'", fen);
+    }
+}
@@ -0,0 +1,98 @@
+fn pawn_score(fen: &str) -> crate::score::Score {
+    crate::pawnstructure::evaluate_pawns(&crate::tests::position_from_fen(fen), &crate::evalparams::DEFAULT_PARAMS).score
+}
+
+fn board(squares: &[&str]) -> crate::bitboard::BitBoard {
+    let mut result = crate::bitboard::BitBoard::new();
+    for s in squares.iter() {
+        result.set(crate::square::Square::from_str(s).unwrap());
+    }
+    result
+}
+
+#[test]
+fn test_startpos_pawns_equal() {
+    //act
+    let entry = crate::pawnstructure::evaluate_pawns(&crate::parser::parse_startpos().unwrap(), &crate::evalparams::DEFAULT_PARAMS);
+
+    //assert
+    assert_eq!(crate::score::Score::default(), entry.score);
+    assert_eq!([board(&[]), board(&[])], entry.passed);
+}
+
+#[test]
+fn test_passed_pawns() {
+    //act
+    let passed = crate::pawnstructure::evaluate_pawns(&crate::tests::position_from_fen("4k3/8/8/3P4/8/2p5/8/4K3 w - - 0 1"), &crate::evalparams::DEFAULT_PARAMS).passed;
+    let not_passed = crate::pawnstructure::evaluate_pawns(&crate::tests::position_from_fen("4k3/4p3/8/3P4/8/8/8/4K3 w - - 0 1"), &crate::evalparams::DEFAULT_PARAMS).passed;
+
+    //assert
+    assert_eq!([board(&["d5"]), board(&["c3"])], passed);
+    assert_eq!([board(&[]), board(&[])], not_passed);
+}
+
+#[test]
+fn test_pawn_structure_penalties() {
+    //doubled against connected
+    assert!(pawn_score("4k3/8/8/8/8/4P3/4P3/4K3 w - - 0 1").mg < pawn_score("4k3/8/8/8/8/3P4/4P3/4K3 w - - 0 1").mg);
+    //isolated against phalanx
+    assert!(pawn_score("4k3/p7/8/8/8/8/P1P5/4K3 w - - 0 1").mg < pawn_score("4k3/p7/8/8/8/8/PP6/4K3 w - - 0 1").mg);
+    //d3 is backward when e5 controls d4
+    assert!(pawn_score("4k3/8/8/4p3/2P5/3P4/8/4K3 w - - 0 1").mg < pawn_score("4k3/8/4p3/8/2P5/3P4/8/4K3 w - - 0 1").mg);
+}
+
+#[test]
+fn test_passed_pawn_free_path() {
+    //arrange
+    let free = crate::tests::position_from_fen("4k3/8/8/3P4/8/8/8/4K3 w - - 0 1");
+    let blocked = crate::tests::position_from_fen("4k3/3n4/8/3P4/8/8/8/4K3 w - - 0 1");
+
+    let params = &crate::evalparams::DEFAULT_PARAMS;
+
+    //act
+    let free_score = crate::pawnstructure::evaluate_passed_pawns(&free, &crate::pawnstructure::evaluate_pawns(&free, params), params);
+    let blocked_score = crate::pawnstructure::evaluate_passed_pawns(&blocked, &crate::pawnstructure::evaluate_pawns(&blocked, params), params);
+
+    //assert
+    assert!(free_score.eg > 0, "No bonus for free path");
+    assert_eq!(crate::score::Score::default(), blocked_score);
+}
+
+#[test]
+fn test_pawn_structure_mirrored() {
+    for fen in crate::bench::BENCH_FENS.iter() {
+        //arrange
+        let position = crate::tests::position_from_fen(fen);
+        let mirrored = position.get_mirrored();
+
+        //act
+        let entry = crate::pawnstructure::evaluate_pawns(&position, &crate::evalparams::DEFAULT_PARAMS);
+        let mirrored_entry = crate::pawnstructure::evaluate_pawns(&mirrored, &crate::evalparams::DEFAULT_PARAMS);
+
+        //assert
+        assert_eq!(-entry.score, mirrored_entry.score, "Pawn score not mirrored for {}", fen);
+        assert_eq!(
+            -crate::pawnstructure::evaluate_passed_pawns(&position, &entry, &crate::evalparams::DEFAULT_PARAMS),
+            crate::pawnstructure::evaluate_passed_pawns(&mirrored, &mirrored_entry, &crate::evalparams::DEFAULT_PARAMS),
+            "Passed pawn score not mirrored for {}", fen
+        );
+    }
+}
+
+#[test]
+fn test_pawn_hash_table_same_result() {
+    //arrange
+    let mut evaluator = crate::evaluation::Evaluator::new();
+
+    for fen in crate::bench::BENCH_FENS.iter() {
+        let position = crate::tests::position_from_fen(fen);
+
+        //act
+        let first = evaluator.evaluate(&position, 0);
+        let cached = evaluator.evaluate(&position, 0);
+
+        //assert
+        assert_eq!(crate::evaluation::evaluate(&position, 0).score(), first.score(), "Evaluator differs for {}", fen);
+        assert_eq!(first.score(), cached.score(), "Cached evaluation differs for {}", fen);
+    }
+}
@@ -0,0 +1,54 @@
+#[test]
+fn test_parse_pgn_openings() {
+    //arrange
+    let pgn = "[Event \"test\"]\n[White \"a\"]\n\n1. e4 {best by test} e5 2. Nf3 (2. f4 exf4) Nc6 $1 1/2-1/2\n\n[Event \"test 2\"]\n[FEN \"4k3/8/8/8/8/8/4P3/4K3 b - - 0 1\"]\n\n1... Kd7 2.e4 *\n";
+
+    //act
+    let openings = crate::pgn::parse_pgn_openings(pgn).unwrap();
+
+    //assert
+    assert_eq!(2, openings.len(), "Unexpected number of openings");
+    let moves = openings[0].moves.iter().map(|m| m.to_fen()).collect::<Vec<String>>();
+    assert_eq!(vec!["e2e4", "e7e5", "g1f3", "b8c6"], moves, "Unexpected opening moves");
+    assert_eq!("4k3/8/8/8/8/8/4P3/4K3 b - - 0 1", openings[1].fen, "Unexpected opening FEN");
+    assert_eq!(2, openings[1].moves.len(), "Unexpected number of moves");
+}
+
+#[test]
+fn test_parse_epd_openings() {
+    //arrange
+    let epd = "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - c0 \"open game\";\n\n";
+
+    //act
+    let openings = crate::pgn::parse_epd_openings(epd).unwrap();
+
+    //assert
+    assert_eq!(1, openings.len(), "Unexpected number of openings");
+    assert_eq!("rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 1", openings[0].fen);
+    assert_eq!(Err(crate::error::HaddockError::InvalidEpd("line 1: incomplete position".to_string())), crate::pgn::parse_epd_openings("8/8/8 w").map(|o| o.len()));
+    assert_eq!(Err(crate::error::HaddockError::InvalidPgn("move e5".to_string())), crate::pgn::parse_pgn_openings("1. e4 e5 2. e5 *").map(|o| o.len()));
+}
+
+#[test]
+fn test_to_pgn_round_trip() {
+    //arrange
+    let opening = crate::pgn::parse_pgn_openings("1. d4 d5 2. c4 dxc4 3. e3 b5 *").unwrap().remove(0);
+    let game = crate::pgn::PgnGame {
+        event: "test".to_string(),
+        round: 1,
+        white: "white".to_string(),
+        black: "black".to_string(),
+        fen: opening.fen.clone(),
+        moves: opening.moves.clone(),
+        result: "*".to_string(),
+        termination: "unterminated".to_string()
+    };
+
+    //act
+    let pgn = crate::pgn::to_pgn(&game);
+    let parsed = crate::pgn::parse_pgn_openings(&pgn).unwrap();
+
+    //assert
+    assert!(pgn.contains("1. d4 d5 2. c4 dxc4 3. e3 b5 *"), "Unexpected movetext\n{}", pgn);
+    assert_eq!(opening.moves, parsed[0].moves, "Moves not preserved");
+}
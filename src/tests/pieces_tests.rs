@@ -0,0 +1,97 @@
+fn pieces(fen: &str) -> crate::score::Score {
+    crate::pieces::evaluate_pieces(&crate::tests::position_from_fen(fen), &crate::evalparams::DEFAULT_PARAMS)
+}
+
+#[test]
+fn test_pieces_startpos_equal() {
+    assert_eq!(crate::score::Score::default(), pieces(crate::global::FEN_STARTPOS));
+}
+
+#[test]
+fn test_mobility() {
+    //arrange
+    let centre = "4k3/8/8/8/4N3/8/8/4K3 w - - 0 1";
+    let corner = "4k3/8/8/8/8/8/8/N3K3 w - - 0 1";
+    //black pawns on e7 and b6 cover d6, f6 and c5
+    let covered = "4k3/4p3/1p6/8/4N3/8/8/4K3 w - - 0 1";
+    let uncovered = "4k3/p6p/8/8/4N3/8/8/4K3 w - - 0 1";
+
+    //act & assert
+    assert!(pieces(corner).mg < pieces(centre).mg, "Knight mobility not scored");
+    assert!(pieces(covered).mg < pieces(uncovered).mg, "Squares attacked by pawns counted");
+}
+
+#[test]
+fn test_rook_files() {
+    //arrange
+    let open = "4k3/pppp4/8/8/8/8/PPPP4/4KR2 w - - 0 1";
+    let semi_open = "4k3/pppp1p2/8/8/8/8/PPPP4/4KR2 w - - 0 1";
+    let closed = "4k3/pppp1p2/8/8/8/8/PPPP1P2/4KR2 w - - 0 1";
+
+    //act & assert
+    assert!(pieces(semi_open).mg < pieces(open).mg, "Open file not better than semi-open");
+    assert!(pieces(closed).mg < pieces(semi_open).mg, "Semi-open file not better than closed");
+}
+
+#[test]
+fn test_rook_on_seventh() {
+    //arrange
+    let seventh = "6k1/R7/8/8/8/8/8/6K1 w - - 0 1";
+    let sixth = "6k1/8/R7/8/8/8/8/6K1 w - - 0 1";
+
+    //act & assert
+    assert!(pieces(sixth).eg < pieces(seventh).eg, "Rook on seventh not rewarded");
+}
+
+#[test]
+fn test_bishop_pair() {
+    //arrange
+    let pair = "4k3/8/8/8/8/8/8/2B1KB2 w - - 0 1";
+    let knight_and_bishop = "4k3/8/8/8/8/8/8/2N1KB2 w - - 0 1";
+
+    //act
+    let pair_score = pieces(pair);
+
+    //assert
+    assert!(pieces(knight_and_bishop).eg < pair_score.eg, "Bishop pair not rewarded");
+    assert!(pair_score.eg >= 50, "Bishop pair bonus missing");
+}
+
+#[test]
+fn test_knight_outpost() {
+    //arrange
+    //knight on d5 supported by the e4 pawn, the c and e pawns of black are gone or passed
+    let outpost = "4k3/pp3ppp/8/3N4/4P3/8/8/4K3 w - - 0 1";
+    let attackable = "4k3/pp2pppp/8/3N4/4P3/8/8/4K3 w - - 0 1";
+
+    //act & assert
+    assert!(pieces(attackable).mg < pieces(outpost).mg, "Knight outpost not rewarded");
+}
+
+#[test]
+fn test_trapped_pieces() {
+    //arrange
+    let trapped_bishop = "4k3/B7/1p6/8/8/8/8/4K3 w - - 0 1";
+    let free_bishop = "4k3/B7/2p5/8/8/8/8/4K3 w - - 0 1";
+    let trapped_rook = "4k3/8/8/8/8/8/5PPP/5K1R w - - 0 1";
+    let castled_rook = "4k3/8/8/8/8/8/5PPP/5RK1 w - - 0 1";
+
+    //act & assert
+    assert!(pieces(trapped_bishop).mg < pieces(free_bishop).mg - 50, "Trapped bishop not penalized");
+    assert!(pieces(trapped_rook).mg < pieces(castled_rook).mg, "Trapped rook not penalized");
+}
+
+#[test]
+fn test_pieces_mirrored() {
+    for fen in crate::bench::BENCH_FENS.iter() {
+        //arrange
+        let position = crate::tests::position_from_fen(fen);
+
+        //act & assert
+        assert_eq!(
+            -crate::pieces::evaluate_pieces(&position, &crate::evalparams::DEFAULT_PARAMS),
+            crate::pieces::evaluate_pieces(&position.get_mirrored(), &crate::evalparams::DEFAULT_PARAMS),
+            "Pieces not mirrored for {}", fen
+        );
+    }
+}
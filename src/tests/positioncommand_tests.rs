@@ -0,0 +1,61 @@
+#[test]
+fn test_parse_startpos_with_moves() {
+    //act
+    let record = crate::positioncommand::parse("startpos moves e2e4 e7e5 g1f3").unwrap();
+
+    //assert
+    assert_eq!("rnbqkbnr/pppp1ppp/8/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2", crate::parser::get_position_fen(record.get_position()));
+    assert_eq!(3, record.get_history().get_len());
+    assert_eq!(3, record.get_moves().len());
+}
+
+#[test]
+fn test_parse_fen_with_moves_and_extra_spaces() {
+    //act
+    let record = crate::positioncommand::parse("  fen 4k3/8/8/8/8/8/4P3/4K3   w - - 0 1  moves e2e4 ").unwrap();
+
+    //assert
+    assert_eq!("4k3/8/8/8/4P3/8/8/4K3 b - e3 0 1", crate::parser::get_position_fen(record.get_position()));
+}
+
+#[test]
+fn test_parse_short_fen() {
+    //act
+    let record = crate::positioncommand::parse("fen 4k3/8/8/8/8/8/8/4K3 b").unwrap();
+
+    //assert
+    assert_eq!("4k3/8/8/8/8/8/8/4K3 b - - 0 1", crate::parser::get_position_fen(record.get_position()));
+}
+
+#[test]
+fn test_parse_castling_and_promotion_moves() {
+    //act
+    let record = crate::positioncommand::parse("fen r3k3/1P6/8/8/8/8/8/4K2R w K - 0 1 moves e1g1 e8d8 b7a8q").unwrap();
+
+    //assert
+    assert_eq!("Q2k4/8/8/8/8/8/8/5RK1 b - - 0 2", crate::parser::get_position_fen(record.get_position()));
+}
+
+#[test]
+fn test_parse_malformed_position_commands() {
+    use crate::error::HaddockError;
+
+    let cases = [
+        ("", HaddockError::MissingPosition),
+        ("moves e2e4", HaddockError::MissingPosition),
+        ("startfen", HaddockError::UnexpectedToken("startfen".to_string())),
+        ("startpos e2e4", HaddockError::UnexpectedToken("e2e4".to_string())),
+        ("fen", HaddockError::FenTooShort),
+        ("fen 4k3/8/8/8/8/8/8/4K3 x - - 0 1", HaddockError::InvalidActiveColor("x".to_string())),
+        ("fen 4k3/8/8/8/8/8/8/4K3 w - - 0 1 e2e4", HaddockError::UnexpectedToken("e2e4".to_string())),
+        ("startpos moves e2e4 e7e5x", HaddockError::InvalidPromotionPiece('x')),
+        ("startpos moves e2e4 e7e", HaddockError::InvalidMove("e7e".to_string())),
+        ("startpos moves e2e5", HaddockError::IllegalMove("e2e5".to_string())),
+        ("startpos moves e3e4", HaddockError::IllegalMove("e3e4".to_string())),
+        ("startpos moves e2e4 e2e4", HaddockError::IllegalMove("e2e4".to_string()))
+    ];
+
+    for (args, expected) in cases.iter() {
+        assert_eq!(Err(expected.clone()), crate::positioncommand::parse(args).map(|_| ()), "Unexpected result for '{}'", args);
+    }
+}
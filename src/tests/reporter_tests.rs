@@ -0,0 +1,73 @@
+#[test]
+fn test_format_info_roundtrip() {
+    //arrange
+    let info = crate::searchevent::SearchInfo {
+        depth: 6,
+        seldepth: 14,
+        multipv: 1,
+        score: crate::outcome::Outcome::Undecided(6, 35),
+        active_color: crate::global::COLOR_BLACK,
+        bound: crate::transposition_table::Bound::Lower,
+        time: 500,
+        nodes: 100000,
+        hashfull: 42,
+        tbhits: 0,
+        pv: vec![crate::move_::Move_::from_str("e7e5").unwrap(), crate::move_::Move_::from_str("g1f3").unwrap()]
+    };
+
+    //act
+    let line = crate::reporter::format_event(&crate::searchevent::SearchEvent::Info(info));
+
+    //assert
+    assert_eq!("info depth 6 seldepth 14 multipv 1 score cp -35 lowerbound time 500 nodes 100000 nps 200000 hashfull 42 tbhits 0 pv e7e5 g1f3", line);
+    match crate::uciclient::parse_engine_line(&line) {
+        crate::uciclient::EngineMessage::Info(parsed) => {
+            assert_eq!(Some(6), parsed.depth);
+            assert_eq!(Some(14), parsed.seldepth);
+            assert_eq!(Some(crate::uciclient::InfoScore::Cp(-35)), parsed.score);
+            assert!(parsed.lowerbound && !parsed.upperbound, "Unexpected bound");
+            assert_eq!(Some(42), parsed.hashfull);
+            assert_eq!(2, parsed.pv.len());
+        }
+        _ => panic!("info line not recognized")
+    }
+}
+
+#[test]
+fn test_format_currmove_and_heartbeat() {
+    //arrange
+    let mv = crate::move_::Move_::from_str("e2e4").unwrap();
+
+    //act
+    let currmove = crate::reporter::format_event(&crate::searchevent::SearchEvent::CurrMove(9, mv, 3));
+    let heartbeat = crate::reporter::format_event(&crate::searchevent::SearchEvent::Heartbeat(2000, 3000000, 250));
+    let bestmove = crate::reporter::format_event(&crate::searchevent::SearchEvent::BestMove(Some(mv)));
+    let nullmove = crate::reporter::format_event(&crate::searchevent::SearchEvent::BestMove(None));
+
+    //assert
+    assert_eq!("info depth 9 currmove e2e4 currmovenumber 3", currmove);
+    assert_eq!("info time 2000 nodes 3000000 nps 1500000 hashfull 250", heartbeat);
+    assert_eq!("bestmove e2e4", bestmove);
+    assert_eq!("bestmove 0000", nullmove);
+}
+
+#[test]
+fn test_transposition_table_hashfull() {
+    //arrange
+    let mut table = crate::transposition_table::TranspositionTable::with_size_mb(1);
+    let mv = crate::move_::Move_::from_str("e2e4").unwrap();
+    let capacity = table.capacity() as u64;
+
+    //act
+    for i in 0..capacity / 2 {
+        table.insert(i, 1, mv, crate::outcome::Outcome::Draw(0, 0), crate::transposition_table::Bound::Exact);
+    }
+
+    //assert
+    assert_eq!(500, table.get_hashfull());
+    assert!(table.get(0, 1).is_some(), "Entry not found");
+    assert!(table.get(0, 2).is_none(), "Entry with too small horizon returned");
+    assert!(table.get(capacity, 1).is_none(), "Entry of other position returned");
+    table.clear();
+    assert_eq!(0, table.get_hashfull());
+}
@@ -0,0 +1,48 @@
+#[test]
+fn test_san_startpos() {
+    //arrange
+    let position = crate::parser::parse_startpos().unwrap();
+    let mv = crate::san::parse_san(&position, "Nf3").unwrap();
+
+    //act
+    let san = crate::san::move_to_san(&position, mv);
+
+    //assert
+    assert_eq!("g1f3", mv.to_fen(), "Unexpected move");
+    assert_eq!("Nf3", san, "Unexpected SAN");
+}
+
+#[test]
+fn test_san_disambiguation() {
+    //arrange
+    //rooks on a2 and h2 can both go to d2, knights on b1 and b5 can both go to c3
+    let position = crate::tests::position_from_fen("4k3/8/8/1N6/8/8/R6R/1N2K3 w - - 0 1");
+
+    //act
+    let rook_move = crate::san::parse_san(&position, "Rad2").unwrap();
+    let knight_move = crate::san::parse_san(&position, "N1c3").unwrap();
+
+    //assert
+    assert_eq!("a2d2", rook_move.to_fen(), "Unexpected rook move");
+    assert_eq!("b1c3", knight_move.to_fen(), "Unexpected knight move");
+    assert_eq!("Rhd2", crate::san::move_to_san(&position, crate::san::parse_san(&position, "h2d2").unwrap()));
+}
+
+#[test]
+fn test_san_castling_promotion_and_mate() {
+    //arrange
+    let position = crate::tests::position_from_fen("4k3/1P6/8/8/8/8/8/4K2R w K - 0 1");
+
+    //act
+    let castle = crate::san::parse_san(&position, "0-0").unwrap();
+    let promo = crate::san::parse_san(&position, "b8=Q+").unwrap();
+
+    //assert
+    assert_eq!("O-O", crate::san::move_to_san(&position, castle), "Unexpected castling SAN");
+    assert_eq!("b8=Q+", crate::san::move_to_san(&position, promo), "Unexpected promotion SAN");
+    assert!(crate::san::parse_san(&position, "Qb8").is_none(), "Illegal move parsed");
+
+    let mate_position = crate::tests::position_from_fen("7k/8/6K1/8/8/8/8/R7 w - - 0 1");
+    let mate = crate::san::parse_san(&mate_position, "Ra8").unwrap();
+    assert_eq!("Ra8#", crate::san::move_to_san(&mate_position, mate), "Unexpected mate SAN");
+}
@@ -0,0 +1,129 @@
+fn create_searcher() -> crate::searcher::Searcher {
+    let (_sender, receiver) = std::sync::mpsc::channel();
+    let position = crate::parser::parse_startpos().unwrap();
+    crate::searcher::Searcher::new(receiver, position, std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)), crate::hash_counter::HashCounter::new())
+}
+
+#[test]
+fn test_tables_kept_between_searches_till_new_game() {
+    //arrange
+    let mut searcher = create_searcher();
+
+    //act
+    searcher.find_best_move(crate::searchtype::SearchType::Depth(4)).unwrap();
+    let nodes_first = searcher.get_node_count();
+    searcher.find_best_move(crate::searchtype::SearchType::Depth(4)).unwrap();
+    let nodes_same_game = searcher.get_node_count();
+    searcher.new_game();
+    searcher.find_best_move(crate::searchtype::SearchType::Depth(4)).unwrap();
+    let nodes_new_game = searcher.get_node_count();
+
+    //assert
+    assert!(nodes_same_game < nodes_first, "Tables not kept between searches");
+    assert_eq!(nodes_first, nodes_new_game, "Tables not cleared on new game");
+}
+
+#[test]
+fn test_no_legal_move() {
+    //arrange
+    let (_sender, receiver) = std::sync::mpsc::channel();
+    let position = crate::tests::position_from_fen("7k/5KQ1/8/8/8/8/8/8 b - - 0 1");
+    let mut searcher = crate::searcher::Searcher::new(receiver, position, std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)), crate::hash_counter::HashCounter::new());
+
+    //act
+    let result = searcher.find_best_move(crate::searchtype::SearchType::Depth(3));
+
+    //assert
+    assert_eq!(Err(crate::error::HaddockError::NoLegalMove), result);
+}
+
+#[test]
+fn test_search_with_tables_from_previous_moves() {
+    //arrange
+    //scores kept from the earlier searches used to give an aspiration window with crossed bounds
+    let mut searcher = create_searcher();
+    let mut record = crate::gamerecord::GameRecord::new(crate::parser::parse_startpos().unwrap());
+    let mut nodes = 0;
+
+    //act
+    for mv in ["b1c3", "b8c6", "e2e4", "g8f6", ""].iter() {
+        searcher.set_position(*record.get_position(), record.get_history().clone());
+        searcher.find_best_move(crate::searchtype::SearchType::Depth(4)).unwrap();
+        nodes = searcher.get_node_count();
+        if !mv.is_empty() {
+            record.apply_move(record.get_position().analyze_move(crate::move_::Move_::from_str(mv).unwrap()));
+        }
+    }
+
+    //assert
+    assert!(nodes < 1_000_000, "Search tree exploded");
+}
+
+#[test]
+fn test_tables_kept_when_root_side_changes_with_contempt() {
+    for &contempt in [0, 50].iter() {
+        //arrange
+        let mut searcher = create_searcher();
+        searcher.set_contempt(contempt);
+        let startpos = crate::parser::parse_startpos().unwrap();
+        let mut after_e4 = startpos;
+        after_e4.apply_move(startpos.analyze_move(crate::move_::Move_::from_str("e2e4").unwrap()));
+
+        //act
+        searcher.find_best_move(crate::searchtype::SearchType::Depth(4)).unwrap();
+        searcher.find_best_move(crate::searchtype::SearchType::Depth(4)).unwrap();
+        let nodes_same_side = searcher.get_node_count();
+        searcher.set_position(after_e4, crate::hash_counter::HashCounter::new());
+        searcher.find_best_move(crate::searchtype::SearchType::Depth(4)).unwrap();
+        searcher.set_position(startpos, crate::hash_counter::HashCounter::new());
+        searcher.find_best_move(crate::searchtype::SearchType::Depth(4)).unwrap();
+        let nodes_side_changed = searcher.get_node_count();
+
+        //assert
+        assert!(nodes_side_changed <= nodes_same_side, "Tables not kept with contempt {}", contempt);
+    }
+}
+
+#[test]
+fn test_stored_draws_scored_with_contempt_of_root_side() {
+    //arrange
+    //every line reaches the fifty move rule, the positions after the best move are stored during the white search
+    let white_root = crate::tests::position_from_fen("4k3/8/8/8/8/8/8/3QK3 w - - 97 80");
+    let mut searcher = create_searcher();
+    searcher.set_contempt(50);
+
+    //act
+    searcher.set_position(white_root, crate::hash_counter::HashCounter::new());
+    let best_move = searcher.find_best_move(crate::searchtype::SearchType::Depth(3)).unwrap();
+    let white_score = searcher.get_best_score();
+    let mut black_root = white_root;
+    black_root.apply_move(best_move);
+    searcher.set_position(black_root, crate::hash_counter::HashCounter::new());
+    searcher.find_best_move(crate::searchtype::SearchType::Depth(2)).unwrap();
+    let black_score = searcher.get_best_score();
+
+    //assert
+    //scores from white's side, a draw is bad for the root side
+    assert_eq!(Some(-50), white_score.map(|s| s.score()));
+    assert_eq!(Some(50), black_score.map(|s| s.score()));
+}
+
+#[test]
+fn test_fifty_move_rule_after_hundred_plies() {
+    for &(halfmoveclock, is_draw) in [(49, false), (98, false), (99, true)].iter() {
+        //arrange
+        let (_sender, receiver) = std::sync::mpsc::channel();
+        let position = crate::tests::position_from_fen(&format!("4k3/8/8/8/8/8/8/3QK3 w - - {} 80", halfmoveclock));
+        let mut searcher = crate::searcher::Searcher::new(receiver, position, std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)), crate::hash_counter::HashCounter::new());
+
+        //act
+        searcher.find_best_move(crate::searchtype::SearchType::Depth(1)).unwrap();
+
+        //assert
+        //every white move keeps the clock running, so only at 99 plies the next move draws
+        match searcher.get_best_score() {
+            Some(crate::outcome::Outcome::Draw(_, _)) => assert!(is_draw, "Draw with halfmove clock {}", halfmoveclock),
+            score => assert!(!is_draw, "No draw with halfmove clock {}: {:?}", halfmoveclock, score)
+        }
+    }
+}
@@ -0,0 +1,33 @@
+#[test]
+fn test_elo_score_conversion() {
+    assert!((crate::sprt::score_to_elo(0.5)).abs() < 1e-9);
+    assert!((crate::sprt::score_to_elo(crate::sprt::elo_to_score(100.0)) - 100.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_match_stats_elo() {
+    //arrange
+    let stats = crate::sprt::MatchStats { wins: 60, losses: 40, draws: 100 };
+
+    //act
+    let elo = stats.get_elo();
+    let error = stats.get_elo_error();
+
+    //assert
+    assert!((elo - 34.9).abs() < 0.1, "Unexpected elo {}", elo);
+    assert!(error > 0.0 && error < elo, "Unexpected error {}", error);
+}
+
+#[test]
+fn test_sprt_result() {
+    //arrange
+    let sprt = crate::sprt::Sprt { elo0: 0.0, elo1: 10.0, alpha: 0.05, beta: 0.05 };
+    let winning = crate::sprt::MatchStats { wins: 600, losses: 400, draws: 1000 };
+    let losing = crate::sprt::MatchStats { wins: 400, losses: 600, draws: 1000 };
+    let even = crate::sprt::MatchStats { wins: 5, losses: 5, draws: 10 };
+
+    //assert
+    assert_eq!(crate::sprt::SprtResult::AcceptH1, winning.get_sprt_result(&sprt));
+    assert_eq!(crate::sprt::SprtResult::AcceptH0, losing.get_sprt_result(&sprt));
+    assert_eq!(crate::sprt::SprtResult::Continue, even.get_sprt_result(&sprt));
+}
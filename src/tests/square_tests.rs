@@ -20,4 +20,10 @@ pub fn test_from_str() {
 
     //assert
     assert_eq!(12, square.to_u32(), "Unexpected square");
-}
\ No newline at end of file
+}
+#[test]
+pub fn test_from_str_errors() {
+    for s in ["", "e", "e0", "e9", "i1", "e22"].iter() {
+        assert!(crate::square::Square::from_str(s) == Err(crate::error::HaddockError::InvalidSquare(s.to_string())), "Unexpected result for '{}'", s);
+    }
+}
@@ -0,0 +1,137 @@
+fn args(text: &str) -> Vec<String> {
+    text.split_whitespace().map(|s| s.to_string()).collect()
+}
+
+fn training_data() -> Vec<crate::trainer::TrainingPosition> {
+    crate::trainer::parse_training_data("\
+rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - ce -30; c9 \"1/2-1/2\";
+4k3/8/8/8/8/8/8/3QK3 w - - ce 900; c9 \"1-0\";
+4k3/8/8/8/8/8/8/3QK3 b - - ce -900; c9 \"1-0\";
+3qk3/8/8/8/8/8/8/4K3 w - - ce -900; c9 \"0-1\";
+4k3/pp6/8/8/8/8/PPP5/4K3 w - - ce 120; c9 \"1-0\";
+4k3/ppp5/8/8/8/8/PP6/4K3 b - - ce 110; c9 \"0-1\";
+").unwrap()
+}
+
+#[test]
+fn test_parse_args() {
+    //act
+    let config = crate::trainer::parse_args(&args("data.epd -output net.bin -epochs 3 -batch 256 -lr 0.01 -optimizer sgd -validation 0.2 -lambda 1 -checkpoint 0 -resume net.bin.ckpt -seed 9")).unwrap();
+
+    //assert
+    assert_eq!("data.epd", config.input);
+    assert_eq!("net.bin", config.output);
+    assert_eq!(3, config.epochs);
+    assert_eq!(256, config.batch_size);
+    assert_eq!(0.01, config.learning_rate);
+    assert_eq!(crate::trainer::Optimizer::Sgd, config.optimizer);
+    assert_eq!(0.2, config.validation);
+    assert_eq!(1.0, config.lambda);
+    assert_eq!(0, config.checkpoint);
+    assert_eq!(Some("net.bin.ckpt".to_string()), config.resume);
+    assert_eq!(9, config.seed);
+    assert!(crate::trainer::parse_args(&args("-epochs 3")).is_err(), "Missing input accepted");
+    assert!(crate::trainer::parse_args(&args("data.epd -optimizer rmsprop")).is_err(), "Unknown optimizer accepted");
+    assert!(crate::trainer::parse_args(&args("data.epd -validation 1")).is_err(), "No training positions accepted");
+}
+
+#[test]
+fn test_parse_training_data() {
+    //act
+    let positions = crate::trainer::parse_training_data("\
+# comment
+rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - ce -30; c9 \"1-0\";
+4k3/8/8/8/8/8/8/3QK3 w - - c9 \"1/2-1/2\";
+").unwrap();
+
+    //assert
+    assert_eq!(2, positions.len());
+    assert_eq!(Some(-30.0), positions[0].score);
+    //result from the side to move
+    assert_eq!(0.0, positions[0].result);
+    assert_eq!(32, positions[0].features[0].len());
+    assert_eq!(None, positions[1].score);
+    assert_eq!(0.5, positions[1].result);
+    assert_eq!(Err(crate::error::HaddockError::InvalidEpd("line 1: no game result".to_string())), crate::trainer::parse_training_data("4k3/8/8/8/8/8/8/3QK3 w - - ce 10;").map(|p| p.len()));
+    assert!(crate::trainer::parse_training_data("4k3/8/8/8/8/8/8/3QK3 w - - ce x; c9 \"1-0\";").is_err(), "Invalid score accepted");
+}
+
+#[test]
+fn test_train_lowers_loss() {
+    for &optimizer in [crate::trainer::Optimizer::Adam, crate::trainer::Optimizer::Sgd].iter() {
+        //arrange
+        let positions = training_data();
+        let mut model = crate::trainer::Model::new(1);
+        let start_loss = model.get_loss(&positions, 0.5);
+        let learning_rate = if optimizer == crate::trainer::Optimizer::Adam { 0.001 } else { 0.5 };
+
+        //act
+        for _ in 0..20 {
+            model.train_batch(&positions, 0.5, optimizer, learning_rate);
+        }
+        let loss = model.get_loss(&positions, 0.5);
+
+        //assert
+        assert!(loss < start_loss, "Loss not lower with {:?}: {} {}", optimizer, start_loss, loss);
+    }
+}
+
+#[test]
+fn test_export_network() {
+    //arrange
+    let mut model = crate::trainer::Model::new(2);
+    let positions = training_data();
+    for _ in 0..5 {
+        model.train_batch(&positions, 0.5, crate::trainer::Optimizer::Adam, 0.01);
+    }
+
+    //act
+    let network = model.to_network();
+
+    //assert
+    //output weights are rounded to 1 / QB, with 256 of them the error adds up to some centipawns
+    for fen in crate::bench::BENCH_FENS.iter() {
+        let position = crate::tests::position_from_fen(fen);
+        let training_position = crate::trainer::get_training_position(&position, None, 0.5);
+        let value = network.evaluate(&network.get_accumulator(&position), position.get_active_color());
+        let expected = model.evaluate(&training_position);
+        assert!((value as f32 - expected).abs() <= 30.0, "Quantized network evaluation {} for {}, {} expected", value, fen, expected);
+    }
+}
+
+#[test]
+fn test_checkpoint() {
+    //arrange
+    let mut model = crate::trainer::Model::new(3);
+    model.train_batch(&training_data(), 0.5, crate::trainer::Optimizer::Adam, 0.001);
+    model.epoch = 4;
+
+    //act
+    let bytes = model.to_checkpoint();
+    let restored = crate::trainer::Model::from_checkpoint(&bytes).unwrap();
+
+    //assert
+    assert_eq!(4, restored.epoch);
+    assert_eq!(bytes, restored.to_checkpoint());
+    assert!(crate::trainer::Model::from_checkpoint(&bytes[..bytes.len() - 4]).is_err(), "Truncated checkpoint accepted");
+    assert!(crate::trainer::Model::from_checkpoint(&model.to_network().to_bytes()).is_err(), "Network file accepted as checkpoint");
+    let network = model.to_network();
+    assert_eq!(network.to_bytes(), crate::trainer::Model::from_network(&network).to_network().to_bytes());
+}
+
+#[test]
+fn test_export_clamped_weights() {
+    //arrange
+    let mut model = crate::trainer::Model::new(4);
+    for p in model.params.iter_mut() {
+        *p = 1000.0;
+    }
+
+    //act
+    //weights are clamped after every batch
+    model.train_batch(&training_data(), 0.5, crate::trainer::Optimizer::Sgd, 0.0);
+    let network = model.to_network();
+
+    //assert
+    assert!(crate::nnue::Network::from_bytes(&network.to_bytes()).is_ok(), "Clamped network rejected by the engine");
+}
@@ -0,0 +1,91 @@
+fn labelled(epd: &str) -> Vec<(crate::position::Position, f64)> {
+    crate::tuner::parse_labelled_epd(epd).unwrap()
+}
+
+#[test]
+fn test_parse_labelled_epd() {
+    //arrange
+    let epd = "\
+# comment
+rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - c9 \"1/2-1/2\";
+4k3/8/8/8/8/8/8/3QK3 w - - c9 \"1-0\";
+4k3/8/8/8/8/8/8/3qK3 w - - 0 1 [0.0]
+";
+
+    //act
+    let positions = labelled(epd);
+
+    //assert
+    assert_eq!(vec![0.5, 1.0, 0.0], positions.iter().map(|p| p.1).collect::<Vec<f64>>());
+    assert_eq!(Err(crate::error::HaddockError::InvalidEpd("line 1: no game result".to_string())), crate::tuner::parse_labelled_epd("4k3/8/8/8/8/8/8/3QK3 w - -").map(|p| p.len()));
+    assert!(crate::tuner::parse_labelled_epd("4k3/8/8/8/8/8/8/3QK3 w \"1-0\"").is_err(), "Incomplete EPD accepted");
+}
+
+#[test]
+fn test_quiet_positions() {
+    //arrange
+    //white takes the hanging queen, the second position is in check and left out
+    let positions = labelled("\
+4k3/8/8/3q4/4P3/8/8/4K3 w - - c9 \"1-0\";
+4k3/8/8/8/8/8/8/R3K2r w - - c9 \"0-1\";
+");
+
+    //act
+    let quiet = crate::tuner::get_quiet_positions(&positions);
+
+    //assert
+    assert_eq!(1, quiet.len());
+    assert_eq!("4k3/8/8/3P4/8/8/8/4K3 b - - 0 1", crate::parser::get_position_fen(&quiet[0].position));
+    assert_eq!(1.0, quiet[0].result);
+}
+
+#[test]
+fn test_sigmoid() {
+    assert_eq!(0.5, crate::tuner::sigmoid(0, 1.0));
+    assert!((crate::tuner::sigmoid(400, 1.0) - 10.0 / 11.0).abs() < 1e-9);
+    assert!((crate::tuner::sigmoid(-400, 1.0) - 1.0 / 11.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_tune_lowers_error() {
+    //arrange
+    //white wins with the bishop pair more often than the evaluation expects
+    let positions = crate::tuner::get_quiet_positions(&labelled("\
+4k3/pppp4/8/8/8/8/PPPP4/2B1KB2 w - - c9 \"1-0\";
+2b1kb2/pppp4/8/8/8/8/PPPP4/4K3 w - - c9 \"0-1\";
+4k3/pppp4/8/8/8/8/PPPP4/2N1KB2 w - - c9 \"1/2-1/2\";
+"));
+    let params = crate::evalparams::EvalParams::default();
+    let start_error = crate::tuner::get_error(&positions, &params, 1.0);
+    let mut iterations = 0;
+
+    //act
+    let tuned = crate::tuner::tune(&positions, &params, 1.0, 20, 1, &mut |_, _, _| iterations += 1);
+
+    //assert
+    assert_eq!(1, iterations);
+    assert!(crate::tuner::get_error(&positions, &tuned, 1.0) < start_error, "Error not lowered");
+}
+
+#[test]
+fn test_parse_tune_args() {
+    //arrange
+    let args = ["data.epd", "-output", "out.json", "-iterations", "5", "-limit", "1000"]
+        .iter().map(|s| s.to_string()).collect::<Vec<String>>();
+
+    //act
+    let config = crate::tuner::parse_args(&args).unwrap();
+
+    //assert
+    assert_eq!("data.epd", config.input);
+    assert_eq!("out.json", config.output);
+    assert_eq!(5, config.iterations);
+    assert_eq!(1000, config.limit);
+    assert!(crate::tuner::parse_args(&["-step".to_string(), "2".to_string()]).is_err(), "Missing input accepted");
+    for (option, value) in [("-step", "4294967297"), ("-iterations", "4294967296"), ("-limit", "-1")] {
+        let args = ["data.epd", option, value].iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        assert_eq!(Err(format!("invalid value {} for {}", value, option)), crate::tuner::parse_args(&args).map(|c| c.step), "Out of range value accepted");
+    }
+    let args = ["data.epd", "-step", "-1"].iter().map(|s| s.to_string()).collect::<Vec<String>>();
+    assert_eq!(Err("step must be at least 1".to_string()), crate::tuner::parse_args(&args).map(|c| c.step));
+}
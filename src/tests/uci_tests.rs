@@ -0,0 +1,33 @@
+#[test]
+fn test_parse_debug() {
+    //act
+    let on = crate::uci::parse("debug on");
+    let off = crate::uci::parse("debug off");
+
+    //assert
+    match (on, off) {
+        (Some(crate::command::InputCommand::Debug(true)), Some(crate::command::InputCommand::Debug(false))) => (),
+        _ => panic!("debug command not recognized")
+    }
+}
+
+#[test]
+fn test_parse_setoption() {
+    //act
+    let file = crate::uci::parse_setoption("name Debug Log File value /tmp/haddock log.txt");
+    let button = crate::uci::parse_setoption("name Clear Hash");
+
+    //assert
+    assert_eq!(Some(("Debug Log File".to_string(), "/tmp/haddock log.txt".to_string())), file);
+    assert_eq!(Some(("Clear Hash".to_string(), "".to_string())), button);
+    assert_eq!(None, crate::uci::parse_setoption("value 5"));
+    assert_eq!(None, crate::uci::parse_setoption("name value 5"));
+}
+
+#[test]
+fn test_parse_eval() {
+    match crate::uci::parse("eval") {
+        Some(crate::command::InputCommand::Eval) => (),
+        _ => panic!("eval command not recognized")
+    }
+}
@@ -0,0 +1,93 @@
+#[test]
+fn test_parse_info_line() {
+    //arrange
+    let line = "info depth 7 seldepth 12 multipv 1 score cp -35 upperbound nodes 12345 nps 100000 hashfull 12 tbhits 0 time 123 pv e2e4 e7e5 g1f3\n";
+
+    //act
+    let message = crate::uciclient::parse_engine_line(line);
+
+    //assert
+    match message {
+        crate::uciclient::EngineMessage::Info(info) => {
+            assert_eq!(Some(7), info.depth);
+            assert_eq!(Some(12), info.seldepth);
+            assert_eq!(Some(1), info.multipv);
+            assert_eq!(Some(crate::uciclient::InfoScore::Cp(-35)), info.score);
+            assert!(info.upperbound && !info.lowerbound, "Unexpected bound");
+            assert_eq!(Some(12345), info.nodes);
+            assert_eq!(Some(100000), info.nps);
+            assert_eq!(Some(12), info.hashfull);
+            assert_eq!(Some(0), info.tbhits);
+            assert_eq!(Some(123), info.time);
+            assert_eq!(vec!["e2e4", "e7e5", "g1f3"], info.pv.iter().map(|m| m.to_fen()).collect::<Vec<String>>());
+        }
+        _ => panic!("info line not recognized")
+    }
+}
+
+#[test]
+fn test_parse_info_currmove_and_string() {
+    //act
+    let currmove = crate::uciclient::parse_engine_line("info currmove e7e8q currmovenumber 3");
+    let string = crate::uciclient::parse_engine_line("info string hello world");
+
+    //assert
+    match currmove {
+        crate::uciclient::EngineMessage::Info(info) => {
+            assert_eq!("e7e8q", info.currmove.unwrap().to_fen());
+            assert_eq!(Some(3), info.currmovenumber);
+        }
+        _ => panic!("info line not recognized")
+    }
+    match string {
+        crate::uciclient::EngineMessage::Info(info) => assert_eq!(Some("hello world".to_string()), info.string),
+        _ => panic!("info line not recognized")
+    }
+}
+
+#[test]
+fn test_parse_bestmove_and_handshake() {
+    assert_eq!(crate::uciclient::EngineMessage::IdName("haddock 0.2.0".to_string()), crate::uciclient::parse_engine_line("id name haddock 0.2.0"));
+    assert_eq!(crate::uciclient::EngineMessage::UciOk, crate::uciclient::parse_engine_line("uciok\r\n"));
+    assert_eq!(crate::uciclient::EngineMessage::ReadyOk, crate::uciclient::parse_engine_line("readyok"));
+
+    match crate::uciclient::parse_engine_line("bestmove e2e4 ponder e7e5") {
+        crate::uciclient::EngineMessage::BestMove(Some(best), Some(ponder)) => {
+            assert_eq!("e2e4", best.to_fen());
+            assert_eq!("e7e5", ponder.to_fen());
+        }
+        _ => panic!("bestmove not recognized")
+    }
+    assert_eq!(crate::uciclient::EngineMessage::BestMove(None, None), crate::uciclient::parse_engine_line("bestmove (none)"));
+}
+
+#[test]
+fn test_parse_option() {
+    //act
+    let message = crate::uciclient::parse_engine_line("option name Debug Log File type string default <empty>");
+    let combo = crate::uciclient::parse_engine_line("option name Style type combo default Normal var Solid var Normal var Risky");
+
+    //assert
+    match message {
+        crate::uciclient::EngineMessage::Option(option) => {
+            assert_eq!("Debug Log File", option.name);
+            assert_eq!("string", option.option_type);
+            assert_eq!(Some("<empty>".to_string()), option.default);
+        }
+        _ => panic!("option not recognized")
+    }
+    match combo {
+        crate::uciclient::EngineMessage::Option(option) => assert_eq!(vec!["Solid", "Normal", "Risky"], option.vars),
+        _ => panic!("option not recognized")
+    }
+}
+
+#[test]
+fn test_position_args() {
+    //arrange
+    let moves = vec![crate::move_::Move_::from_str("e2e4").unwrap()];
+
+    //act & assert
+    assert_eq!("startpos moves e2e4", crate::uciclient::get_position_args(crate::global::FEN_STARTPOS, &moves));
+    assert_eq!("fen 8/8/8/8/8/8/8/K6k w - - 0 1", crate::uciclient::get_position_args("8/8/8/8/8/8/8/K6k w - - 0 1", &[]));
+}
@@ -0,0 +1,46 @@
+fn validate_fen(fen: &str) -> Result<(), crate::error::HaddockError> {
+    crate::validation::validate_position(&crate::tests::position_from_fen(fen))
+}
+
+#[test]
+fn test_validate_legal_positions() {
+    assert_eq!(Ok(()), validate_fen(crate::global::FEN_STARTPOS));
+    assert_eq!(Ok(()), validate_fen("rnbqkbnr/ppp1pppp/8/8/3pP3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 3"));
+    assert_eq!(Ok(()), validate_fen("QQQQQQQQ/Q7/8/8/8/8/8/4K2k b - - 0 1"));
+    for fen in crate::bench::BENCH_FENS.iter() {
+        assert_eq!(Ok(()), validate_fen(fen), "Bench position {} invalid", fen);
+    }
+}
+
+#[test]
+fn test_validate_illegal_positions() {
+    use crate::error::HaddockError;
+
+    let white = crate::global::COLOR_WHITE;
+    let black = crate::global::COLOR_BLACK;
+    let cases = [
+        ("8/8/8/8/8/8/8/8 w - - 0 1", HaddockError::KingMissing(white)),
+        ("8/8/8/8/8/8/8/4K3 w - - 0 1", HaddockError::KingMissing(black)),
+        ("4k3/8/8/8/8/8/8/3KK3 w - - 0 1", HaddockError::TooManyKings(white)),
+        ("4k3/8/8/8/8/P7/PPPPPPPP/4K3 w - - 0 1", HaddockError::TooManyPawns(white)),
+        ("QQQQQQQQ/Q7/8/8/8/8/P7/4K2k w - - 0 1", HaddockError::TooManyPieces(white)),
+        ("4k1P1/8/8/8/8/8/8/4K3 w - - 0 1", HaddockError::PawnOnBackRank("g8".to_string())),
+        ("4k3/8/8/8/8/8/8/p3K3 w - - 0 1", HaddockError::PawnOnBackRank("a1".to_string())),
+        ("4k3/8/8/8/8/8/8/4K3 w K - 0 1", HaddockError::InvalidCastlingRights('K')),
+        ("r2k4/8/8/8/8/8/8/4K3 w q - 0 1", HaddockError::InvalidCastlingRights('q')),
+        ("4k3/8/8/8/8/8/8/4R1K1 w - - 0 1", HaddockError::OpponentInCheck),
+        ("4k3/8/8/8/8/8/8/4K3 b - e3 0 1", HaddockError::InvalidEnPassantSquare("e3".to_string())),
+        ("4k3/8/8/8/4P3/8/8/4K3 w - e3 0 1", HaddockError::InvalidEnPassantSquare("e3".to_string())),
+        ("4k3/8/8/8/4P3/4N3/8/4K3 b - e3 0 1", HaddockError::InvalidEnPassantSquare("e3".to_string()))
+    ];
+
+    for (fen, expected) in cases.iter() {
+        assert_eq!(Err(expected.clone()), validate_fen(fen), "Unexpected result for '{}'", fen);
+    }
+}
+
+#[test]
+fn test_position_command_rejects_illegal_fen() {
+    let result = crate::positioncommand::parse("fen 4k3/8/8/8/8/8/8/4R1K1 w - - 0 1 moves g1g2");
+    assert_eq!(Some(crate::error::HaddockError::OpponentInCheck), result.err());
+}
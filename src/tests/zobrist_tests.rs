@@ -44,12 +44,13 @@ pub fn test_same_transposition_hash() {
 }
 
 #[test]
-pub fn test_ep_square_hash_different() {
+pub fn test_ep_square_without_capture_hash_equal() {
     //arrange
     let mut position1 = crate::parser::parse_startpos().unwrap();
     let mut position2 = crate::parser::parse_startpos().unwrap();
 
     //NOTE
+    //the ep square after d2d4 or e2e4 cannot be used by black, so it is not part of the hash
     let mv_e2e4 = crate::move_::Move_::from_str("e2e4").unwrap();
     let mv_e7e5 = crate::move_::Move_::from_str("e7e5").unwrap();
     let mv_d2d4 = crate::move_::Move_::from_str("d2d4").unwrap();
@@ -72,8 +73,76 @@ pub fn test_ep_square_hash_different() {
     //assert
     let hash1 = position1.get_hash();
     let hash2 = position2.get_hash();
-    assert_ne!(hash1, hash2, "hashes should not be equal");
+    assert_eq!(hash1, hash2, "hashes should be equal");
+}
+
+fn apply_moves(moves: &[&str]) -> crate::position::Position {
+    let mut position = crate::parser::parse_startpos().unwrap();
+    for m in moves.iter() {
+        let mv = position.analyze_move(crate::move_::Move_::from_str(m).unwrap());
+        position.apply_move(mv);
+    }
+    position
+}
+
+fn hash_fen(fen: &str) -> u64 {
+    crate::tests::position_from_fen(fen).get_hash()
+}
+
+#[test]
+pub fn test_ep_square_with_capture_hash_different() {
+    //arrange
+    //same placement, only after d7d5 white can capture e5d6
+    let moves1 = ["e2e4", "g8f6", "e4e5", "d7d5"];
+    let moves2 = ["e2e4", "d7d5", "e4e5", "g8f6"];
+
+    //act
+    let position1 = apply_moves(&moves1);
+    let position2 = apply_moves(&moves2);
+
+    //assert
+    assert_ne!(position1.get_hash(), position2.get_hash(), "hashes should not be equal");
+}
+
+#[test]
+pub fn test_ep_square_pinned_pawn_hash_equal() {
+    //arrange
+    //b5c6 would expose the white king to the rook on h5
+    let pinned_ep = "8/8/8/KPp4r/8/8/8/4k3 w - c6 0 2";
+    let pinned_no_ep = "8/8/8/KPp4r/8/8/8/4k3 w - - 0 2";
+    let free_ep = "8/8/8/1Pp4r/K7/8/8/4k3 w - c6 0 2";
+    let free_no_ep = "8/8/8/1Pp4r/K7/8/8/4k3 w - - 0 2";
+
+    //act & assert
+    assert_eq!(hash_fen(pinned_ep), hash_fen(pinned_no_ep), "hashes should be equal");
+    assert_ne!(hash_fen(free_ep), hash_fen(free_no_ep), "hashes should not be equal");
 }
 
+#[test]
+pub fn test_incremental_hash_random_games() {
+    use rand::{Rng, SeedableRng};
+    use rand::rngs::StdRng;
+
+    //arrange
+    let mut rng: StdRng = SeedableRng::from_seed([42; 32]);
 
+    for _ in 0..50 {
+        let mut position = crate::parser::parse_startpos().unwrap();
 
+        for _ in 0..150 {
+            let moves = crate::generator::Generator::new(&position).generate_legal_moves();
+            if moves.is_empty() {
+                break;
+            }
+
+            //act
+            position.apply_move(moves[rng.gen_range(0, moves.len())]);
+
+            //assert
+            let mut fresh = position;
+            fresh.generate_new_hash();
+            assert_eq!(fresh.get_hash(), position.get_hash(), "incremental hash differs in\n{}", position);
+            assert_eq!(fresh.get_pawn_hash(), position.get_pawn_hash(), "incremental pawn hash differs in\n{}", position);
+        }
+    }
+}
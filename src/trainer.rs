@@ -0,0 +1,500 @@
+use std::fs;
+
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+
+use crate::error::HaddockError;
+use crate::global;
+use crate::nnue;
+use crate::nnue::{ Network, HIDDEN_SIZE, INPUT_SIZE };
+use crate::parser;
+use crate::position::Position;
+use crate::tuner;
+
+const DEFAULT_OUTPUT: &str = "network.bin";
+const DEFAULT_EPOCHS: u32 = 10;
+const DEFAULT_BATCH_SIZE: usize = 1024;
+const DEFAULT_LEARNING_RATE: f32 = 0.001;
+const DEFAULT_VALIDATION: f32 = 0.1;
+//share of the search score in the target, the rest is the game result
+const DEFAULT_LAMBDA: f32 = 0.5;
+//positions shown with their evaluation after training
+const SAMPLE_COUNT: usize = 5;
+
+//parameters in one vector, so the optimizers handle them all the same way
+const FEATURE_WEIGHTS: usize = 0;
+const FEATURE_BIAS: usize = FEATURE_WEIGHTS + INPUT_SIZE * HIDDEN_SIZE;
+const OUTPUT_WEIGHTS: usize = FEATURE_BIAS + HIDDEN_SIZE;
+const OUTPUT_BIAS: usize = OUTPUT_WEIGHTS + 2 * HIDDEN_SIZE;
+pub const PARAM_COUNT: usize = OUTPUT_BIAS + 1;
+
+//quantized feature weights and biases stay within the limit of the engine, so its accumulator can not overflow
+//output weights only have to fit in i16, the engine sums the output in i64
+const MAX_FEATURE_WEIGHT: f32 = nnue::MAX_FEATURE_WEIGHT as f32 / nnue::QA as f32;
+const MAX_OUTPUT_WEIGHT: f32 = i16::MAX as f32 / nnue::QB as f32;
+
+const ADAM_BETA_1: f32 = 0.9;
+const ADAM_BETA_2: f32 = 0.999;
+const ADAM_EPSILON: f32 = 1e-8;
+
+//"HDTC", version, input and hidden size, epoch and optimizer step as u32, then weights and adam moments as f32
+const CHECKPOINT_MAGIC: &[u8; 4] = b"HDTC";
+const CHECKPOINT_VERSION: u32 = 1;
+const CHECKPOINT_HEADER_SIZE: usize = 24;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Optimizer {
+    Sgd,
+    Adam
+}
+
+pub struct TrainConfig {
+    pub input: String,
+    pub output: String,
+    pub epochs: u32,
+    pub batch_size: usize,
+    pub learning_rate: f32,
+    pub optimizer: Optimizer,
+    pub validation: f32, //share of the positions held back
+    pub lambda: f32,
+    pub checkpoint: u32, //epochs between checkpoints, 0 for none
+    pub resume: Option<String>,
+    pub seed: u64
+}
+
+//active features of both sides, side to move first, with score and result from the side to move
+pub struct TrainingPosition {
+    pub features: [Vec<u16>; 2],
+    pub score: Option<f32>,
+    pub result: f32
+}
+
+//float weights of the network with the optimizer state
+pub struct Model {
+    pub params: Vec<f32>,
+    moments: Vec<f32>,
+    velocities: Vec<f32>,
+    step: u32,
+    pub epoch: u32
+}
+
+//<file.epd> -output <file> -epochs <n> -batch <n> -lr <x> -optimizer sgd|adam -validation <x> -lambda <x>
+//-checkpoint <n> -resume <file> -seed <n>
+pub fn parse_args(args: &[String]) -> Result<TrainConfig, String> {
+    let mut config = TrainConfig {
+        input: String::new(),
+        output: DEFAULT_OUTPUT.to_string(),
+        epochs: DEFAULT_EPOCHS,
+        batch_size: DEFAULT_BATCH_SIZE,
+        learning_rate: DEFAULT_LEARNING_RATE,
+        optimizer: Optimizer::Adam,
+        validation: DEFAULT_VALIDATION,
+        lambda: DEFAULT_LAMBDA,
+        checkpoint: 1,
+        resume: None,
+        seed: 0
+    };
+
+    let mut i = 0;
+    while i < args.len() {
+        let option = args[i].as_str();
+        if !option.starts_with('-') {
+            if !config.input.is_empty() {
+                return Err(format!("unexpected argument {}", option));
+            }
+            config.input = option.to_string();
+            i += 1;
+            continue;
+        }
+
+        let value = args.get(i + 1).ok_or(format!("{} expects one value", option))?;
+        let number = || value.parse::<u64>().map_err(|_| format!("invalid value {} for {}", value, option));
+        let fraction = || value.parse::<f32>().map_err(|_| format!("invalid value {} for {}", value, option));
+        match option {
+            "-output" => config.output = value.to_string(),
+            "-epochs" => config.epochs = number()? as u32,
+            "-batch" => config.batch_size = number()? as usize,
+            "-lr" => config.learning_rate = fraction()?,
+            "-optimizer" => config.optimizer = match value.as_str() {
+                "sgd" => Optimizer::Sgd,
+                "adam" => Optimizer::Adam,
+                _ => return Err(format!("unknown optimizer {}", value))
+            },
+            "-validation" => config.validation = fraction()?,
+            "-lambda" => config.lambda = fraction()?,
+            "-checkpoint" => config.checkpoint = number()? as u32,
+            "-resume" => config.resume = Some(value.to_string()),
+            "-seed" => config.seed = number()?,
+            _ => return Err(format!("unknown option {}", option))
+        }
+        i += 2;
+    }
+
+    if config.input.is_empty() {
+        return Err("no input file".to_string());
+    }
+    if config.batch_size == 0 {
+        return Err("batch size must be at least 1".to_string());
+    }
+    if !(0.0..1.0).contains(&config.validation) {
+        return Err("validation must be at least 0 and below 1".to_string());
+    }
+    if !(0.0..=1.0).contains(&config.lambda) {
+        return Err("lambda must be between 0 and 1".to_string());
+    }
+    Ok(config)
+}
+
+//epd lines as written by gensfens, "ce" is the score of the side to move and optional
+pub fn parse_training_data(content: &str) -> Result<Vec<TrainingPosition>, HaddockError> {
+    let mut result = Vec::new();
+
+    for (i, line) in content.lines().enumerate() {
+        let fen_parts = line.split_whitespace().take(4).collect::<Vec<&str>>();
+        if fen_parts.is_empty() || fen_parts[0].starts_with('#') {
+            continue;
+        }
+        if fen_parts.len() < 4 {
+            return Err(HaddockError::InvalidEpd(format!("line {}: incomplete position", i + 1)));
+        }
+
+        let position = parser::parse_fen(&fen_parts).map_err(|e| HaddockError::InvalidEpd(format!("line {}: {}", i + 1, e)))?;
+        let operations = line.split_whitespace().skip(4).collect::<Vec<&str>>().join(" ");
+        let game_result = tuner::get_result(&operations).ok_or_else(|| HaddockError::InvalidEpd(format!("line {}: no game result", i + 1)))?;
+        let score = match operations.split(';').map(|o| o.trim()).find(|o| o.starts_with("ce ")) {
+            Some(o) => Some(o[3..].trim().parse::<f32>().map_err(|_| HaddockError::InvalidEpd(format!("line {}: invalid score {}", i + 1, o)))?),
+            None => None
+        };
+        result.push(get_training_position(&position, score, game_result as f32));
+    }
+
+    Ok(result)
+}
+
+//score from the side to move, result from white's side
+pub fn get_training_position(position: &Position, score: Option<f32>, white_result: f32) -> TrainingPosition {
+    let active_color = position.get_active_color();
+    let mut features = [Vec::new(), Vec::new()];
+    for (piece, square) in position.get_all_active_pieces() {
+        features[0].push(nnue::get_feature_index(active_color, piece, square) as u16);
+        features[1].push(nnue::get_feature_index(1 - active_color, piece, square) as u16);
+    }
+
+    TrainingPosition {
+        features,
+        score,
+        result: if active_color == global::COLOR_WHITE { white_result } else { 1.0 - white_result }
+    }
+}
+
+fn sigmoid(value: f32) -> f32 {
+    1.0 / (1.0 + (-value).exp())
+}
+
+impl Model {
+    //small random weights
+    pub fn new(seed: u64) -> Model {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut params = vec![0.0; PARAM_COUNT];
+        for p in params[FEATURE_WEIGHTS..FEATURE_BIAS].iter_mut() {
+            *p = rng.gen_range(-0.1, 0.1);
+        }
+        for p in params[OUTPUT_WEIGHTS..OUTPUT_BIAS].iter_mut() {
+            *p = rng.gen_range(-0.1, 0.1);
+        }
+        Model::from_params(params)
+    }
+
+    //the weights of a network file, to train it further
+    pub fn from_network(network: &Network) -> Model {
+        let mut params = vec![0.0; PARAM_COUNT];
+        let qa = nnue::QA as f32;
+        let qb = nnue::QB as f32;
+        for (p, w) in params[FEATURE_WEIGHTS..FEATURE_BIAS].iter_mut().zip(network.feature_weights.iter()) {
+            *p = *w as f32 / qa;
+        }
+        for (p, w) in params[FEATURE_BIAS..OUTPUT_WEIGHTS].iter_mut().zip(network.feature_bias.iter()) {
+            *p = *w as f32 / qa;
+        }
+        for (p, w) in params[OUTPUT_WEIGHTS..OUTPUT_BIAS].iter_mut().zip(network.output_weights.iter()) {
+            *p = *w as f32 / qb;
+        }
+        params[OUTPUT_BIAS] = network.output_bias as f32 / (qa * qb);
+        Model::from_params(params)
+    }
+
+    fn from_params(params: Vec<f32>) -> Model {
+        Model {
+            params,
+            moments: vec![0.0; PARAM_COUNT],
+            velocities: vec![0.0; PARAM_COUNT],
+            step: 0,
+            epoch: 0
+        }
+    }
+
+    //network output before the sigmoid and the hidden values of both sides
+    fn forward(&self, position: &TrainingPosition) -> (f32, [Vec<f32>; 2]) {
+        let mut hidden = [self.params[FEATURE_BIAS..OUTPUT_WEIGHTS].to_vec(), self.params[FEATURE_BIAS..OUTPUT_WEIGHTS].to_vec()];
+        let mut output = self.params[OUTPUT_BIAS];
+        for (side, values) in hidden.iter_mut().enumerate() {
+            for &feature in position.features[side].iter() {
+                let start = FEATURE_WEIGHTS + feature as usize * HIDDEN_SIZE;
+                for (h, w) in values.iter_mut().zip(self.params[start..start + HIDDEN_SIZE].iter()) {
+                    *h += *w;
+                }
+            }
+            let weights = &self.params[OUTPUT_WEIGHTS + side * HIDDEN_SIZE..OUTPUT_WEIGHTS + (side + 1) * HIDDEN_SIZE];
+            for (h, w) in values.iter().zip(weights.iter()) {
+                output += h.clamp(0.0, 1.0) * w;
+            }
+        }
+        (output, hidden)
+    }
+
+    //centipawns from the side to move
+    pub fn evaluate(&self, position: &TrainingPosition) -> f32 {
+        self.forward(position).0 * nnue::SCALE as f32
+    }
+
+    //mean squared error between the expected scores of the network and the targets
+    pub fn get_loss(&self, positions: &[TrainingPosition], lambda: f32) -> f32 {
+        if positions.is_empty() {
+            return 0.0;
+        }
+        let total: f32 = positions.iter()
+            .map(|p| (sigmoid(self.forward(p).0) - get_target(p, lambda)).powi(2))
+            .sum();
+        total / positions.len() as f32
+    }
+
+    //one optimizer step on the mean gradient of the batch, returns the loss of the batch
+    pub fn train_batch(&mut self, batch: &[TrainingPosition], lambda: f32, optimizer: Optimizer, learning_rate: f32) -> f32 {
+        if batch.is_empty() {
+            return 0.0;
+        }
+
+        let mut gradients = vec![0.0; PARAM_COUNT];
+        let mut loss = 0.0;
+        for position in batch {
+            let (output, hidden) = self.forward(position);
+            let prediction = sigmoid(output);
+            let error = prediction - get_target(position, lambda);
+            loss += error * error;
+
+            let gradient = 2.0 * error * prediction * (1.0 - prediction);
+            gradients[OUTPUT_BIAS] += gradient;
+            for (side, values) in hidden.iter().enumerate() {
+                for (i, &h) in values.iter().enumerate() {
+                    let output_index = OUTPUT_WEIGHTS + side * HIDDEN_SIZE + i;
+                    gradients[output_index] += gradient * h.clamp(0.0, 1.0);
+
+                    //the clipped relu passes gradients only between 0 and 1
+                    if h <= 0.0 || h >= 1.0 {
+                        continue;
+                    }
+                    let hidden_gradient = gradient * self.params[output_index];
+                    gradients[FEATURE_BIAS + i] += hidden_gradient;
+                    for &feature in position.features[side].iter() {
+                        gradients[FEATURE_WEIGHTS + feature as usize * HIDDEN_SIZE + i] += hidden_gradient;
+                    }
+                }
+            }
+        }
+
+        let scale = 1.0 / batch.len() as f32;
+        for g in gradients.iter_mut() {
+            *g *= scale;
+        }
+        self.apply_gradients(&gradients, optimizer, learning_rate);
+        loss * scale
+    }
+
+    fn apply_gradients(&mut self, gradients: &[f32], optimizer: Optimizer, learning_rate: f32) {
+        self.step += 1;
+        match optimizer {
+            Optimizer::Sgd => {
+                for (p, g) in self.params.iter_mut().zip(gradients.iter()) {
+                    *p -= learning_rate * g;
+                }
+            }
+            Optimizer::Adam => {
+                let correction_1 = 1.0 - ADAM_BETA_1.powi(self.step as i32);
+                let correction_2 = 1.0 - ADAM_BETA_2.powi(self.step as i32);
+                for (i, &g) in gradients.iter().enumerate() {
+                    //features not in the batch keep their weights
+                    if g == 0.0 && self.moments[i] == 0.0 {
+                        continue;
+                    }
+                    self.moments[i] = ADAM_BETA_1 * self.moments[i] + (1.0 - ADAM_BETA_1) * g;
+                    self.velocities[i] = ADAM_BETA_2 * self.velocities[i] + (1.0 - ADAM_BETA_2) * g * g;
+                    let moment = self.moments[i] / correction_1;
+                    let velocity = self.velocities[i] / correction_2;
+                    self.params[i] -= learning_rate * moment / (velocity.sqrt() + ADAM_EPSILON);
+                }
+            }
+        }
+
+        for p in self.params[FEATURE_WEIGHTS..OUTPUT_WEIGHTS].iter_mut() {
+            *p = p.clamp(-MAX_FEATURE_WEIGHT, MAX_FEATURE_WEIGHT);
+        }
+        for p in self.params[OUTPUT_WEIGHTS..OUTPUT_BIAS].iter_mut() {
+            *p = p.clamp(-MAX_OUTPUT_WEIGHT, MAX_OUTPUT_WEIGHT);
+        }
+    }
+
+    //quantized weights as used by the engine
+    pub fn to_network(&self) -> Network {
+        let qa = nnue::QA as f32;
+        let qb = nnue::QB as f32;
+        let mut network = Network::new();
+        for (w, p) in network.feature_weights.iter_mut().zip(self.params[FEATURE_WEIGHTS..FEATURE_BIAS].iter()) {
+            *w = (p * qa).round() as i16;
+        }
+        for (w, p) in network.feature_bias.iter_mut().zip(self.params[FEATURE_BIAS..OUTPUT_WEIGHTS].iter()) {
+            *w = (p * qa).round() as i16;
+        }
+        for (w, p) in network.output_weights.iter_mut().zip(self.params[OUTPUT_WEIGHTS..OUTPUT_BIAS].iter()) {
+            *w = (p * qb).round() as i16;
+        }
+        network.output_bias = (self.params[OUTPUT_BIAS] * qa * qb).round() as i32;
+        network
+    }
+
+    pub fn to_checkpoint(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(CHECKPOINT_HEADER_SIZE + 3 * 4 * PARAM_COUNT);
+        bytes.extend_from_slice(CHECKPOINT_MAGIC);
+        for n in [CHECKPOINT_VERSION, INPUT_SIZE as u32, HIDDEN_SIZE as u32, self.epoch, self.step] {
+            bytes.extend_from_slice(&n.to_le_bytes());
+        }
+        for values in [&self.params, &self.moments, &self.velocities] {
+            for v in values.iter() {
+                bytes.extend_from_slice(&v.to_le_bytes());
+            }
+        }
+        bytes
+    }
+
+    pub fn from_checkpoint(bytes: &[u8]) -> Result<Model, String> {
+        let expected_size = CHECKPOINT_HEADER_SIZE + 3 * 4 * PARAM_COUNT;
+        if bytes.len() < CHECKPOINT_HEADER_SIZE || &bytes[0..4] != CHECKPOINT_MAGIC {
+            return Err("not a checkpoint file".to_string());
+        }
+        let header = (1..6).map(|i| read_u32(bytes, 4 * i)).collect::<Vec<u32>>();
+        if header[0] != CHECKPOINT_VERSION || header[1] as usize != INPUT_SIZE || header[2] as usize != HIDDEN_SIZE {
+            return Err("checkpoint of another network version or size".to_string());
+        }
+        if bytes.len() != expected_size {
+            return Err(format!("checkpoint has {} bytes, {} expected", bytes.len(), expected_size));
+        }
+
+        let mut model = Model::from_params(vec![0.0; PARAM_COUNT]);
+        model.epoch = header[3];
+        model.step = header[4];
+        let mut offset = CHECKPOINT_HEADER_SIZE;
+        for values in [&mut model.params, &mut model.moments, &mut model.velocities] {
+            for v in values.iter_mut() {
+                *v = f32::from_bits(read_u32(bytes, offset));
+                offset += 4;
+            }
+        }
+        Ok(model)
+    }
+
+    pub fn save_checkpoint(&self, path: &str) -> Result<(), String> {
+        fs::write(path, self.to_checkpoint()).map_err(|e| format!("cannot write {}: {}", path, e))
+    }
+
+    //checkpoints continue with their optimizer state, network files start a new one
+    pub fn load(path: &str) -> Result<Model, String> {
+        let bytes = fs::read(path).map_err(|e| format!("cannot read {}: {}", path, e))?;
+        if bytes.starts_with(CHECKPOINT_MAGIC) {
+            Model::from_checkpoint(&bytes)
+        } else {
+            Network::from_bytes(&bytes).map(|n| Model::from_network(&n)).map_err(|e| e.to_string())
+        }
+    }
+}
+
+//expected score for the side to move, the search score blended with the game result
+fn get_target(position: &TrainingPosition, lambda: f32) -> f32 {
+    match position.score {
+        Some(score) => lambda * sigmoid(score / nnue::SCALE as f32) + (1.0 - lambda) * position.result,
+        None => position.result
+    }
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([bytes[offset], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]])
+}
+
+//the network with the lowest validation loss is written after every epoch that improves it,
+//the checkpoint <output>.ckpt after every checkpoint interval
+pub fn run(config: &TrainConfig) -> Result<Model, String> {
+    let content = fs::read_to_string(&config.input).map_err(|e| format!("cannot read {}: {}", config.input, e))?;
+    let mut positions = parse_training_data(&content).map_err(|e| e.to_string())?;
+    if positions.is_empty() {
+        return Err("no positions to train with".to_string());
+    }
+
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    positions.shuffle(&mut rng);
+    let validation_count = (positions.len() as f32 * config.validation) as usize;
+    let mut training = positions.split_off(validation_count);
+    let validation = positions;
+    println!("Read {} positions from {}, {} for training, {} for validation",
+        training.len() + validation.len(), config.input, training.len(), validation.len());
+
+    let mut model = match &config.resume {
+        Some(path) => {
+            let m = Model::load(path)?;
+            println!("Resuming from {} after epoch {}", path, m.epoch);
+            m
+        }
+        None => Model::new(config.seed)
+    };
+
+    let checkpoint_path = format!("{}.ckpt", config.output);
+    //a resumed network is only replaced by one with a lower loss
+    let mut best_loss = match &config.resume {
+        Some(_) => {
+            let loss = model.get_loss(if validation.is_empty() { &training } else { &validation }, config.lambda);
+            println!("Loss of the resumed network {:.6}", loss);
+            Some(loss)
+        }
+        None => None
+    };
+    let mut written = false;
+    while model.epoch < config.epochs {
+        training.shuffle(&mut rng);
+        let mut training_loss = 0.0;
+        for batch in training.chunks(config.batch_size) {
+            training_loss += model.train_batch(batch, config.lambda, config.optimizer, config.learning_rate) * batch.len() as f32;
+        }
+        training_loss /= training.len() as f32;
+        model.epoch += 1;
+
+        //without validation positions the training loss decides
+        let loss = if validation.is_empty() { training_loss } else { model.get_loss(&validation, config.lambda) };
+        println!("Epoch {}/{}, training loss {:.6}, validation loss {:.6}", model.epoch, config.epochs, training_loss, loss);
+
+        if best_loss.is_none_or(|best| loss < best) {
+            best_loss = Some(loss);
+            model.to_network().save(&config.output).map_err(|e| e.to_string())?;
+            written = true;
+        }
+        if config.checkpoint > 0 && model.epoch % config.checkpoint == 0 {
+            model.save_checkpoint(&checkpoint_path)?;
+        }
+    }
+
+    if written {
+        println!("Network written to {}", config.output);
+    }
+    for position in validation.iter().chain(training.iter()).take(SAMPLE_COUNT) {
+        let score = position.score.map_or("none".to_string(), |s| s.to_string());
+        println!("Sample evaluation {:.0}, search score {}, result {}", model.evaluate(position), score, position.result);
+    }
+    Ok(model)
+}
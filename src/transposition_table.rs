@@ -1,9 +1,8 @@
-use std::collections::HashMap;
-
-use crate::hash_key_hasher::HashKeyBuildHasher;
 use crate::move_::Move_;
 use crate::outcome::Outcome;
 
+pub const DEFAULT_SIZE_MB: usize = 16;
+
 #[derive(Copy, Clone, PartialEq, Eq)]
 pub enum Bound {
     Exact,
@@ -12,45 +11,88 @@ pub enum Bound {
 
 #[derive(Copy, Clone, PartialEq, Eq)]
 pub struct TranspositionTableEntry {
+    pub hash_key: u64,
     pub horizon: i32,
     pub best_move: Move_,
     pub outcome: Outcome,
     pub bound: Bound
 }
 
-pub struct TranspositionTable(HashMap<u64, TranspositionTableEntry, HashKeyBuildHasher>);
+//fixed size table, the slot is selected by the lowest bits of the hash key
+pub struct TranspositionTable {
+    entries: Vec<Option<TranspositionTableEntry>>,
+    mask: u64,
+    len: usize
+}
 
 impl TranspositionTable {
     pub fn new() -> Self {
-        TranspositionTable(HashMap::default())
+        TranspositionTable::with_size_mb(DEFAULT_SIZE_MB)
+    }
+
+    pub fn with_size_mb(size_mb: usize) -> Self {
+        let entry_size = std::mem::size_of::<Option<TranspositionTableEntry>>();
+        let max_entries = std::cmp::max(size_mb * 1024 * 1024 / entry_size, 1);
+
+        //round down to a power of two
+        let mut size: usize = 1;
+        while size * 2 <= max_entries {
+            size *= 2;
+        }
+
+        TranspositionTable {
+            entries: vec![None; size],
+            mask: (size - 1) as u64,
+            len: 0
+        }
     }
 
     #[allow(dead_code)]
     pub fn len(&self) -> usize {
-        self.0.len()
+        self.len
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn clear(&mut self) {
+        for e in self.entries.iter_mut() {
+            *e = None;
+        }
+        self.len = 0;
+    }
+
+    //occupation in permille, as reported by uci hashfull
+    pub fn get_hashfull(&self) -> u32 {
+        (self.len * 1000 / self.capacity()) as u32
     }
 
     pub fn insert(&mut self, hash_key: u64, horizon: i32, best_move: Move_, outcome: Outcome, bound: Bound) {
-        match self.0.get_mut(&hash_key) {
+        let index = (hash_key & self.mask) as usize;
+        match &mut self.entries[index] {
             Some(e) => {
-                if horizon >= e.horizon {
-                    e.horizon = horizon;
-                    e.best_move = best_move;
-                    e.outcome = outcome;
-                    e.bound = bound;
+                //same position: keep the deepest result, other position: always replace
+                if e.hash_key != hash_key || horizon >= e.horizon {
+                    *e = TranspositionTableEntry { hash_key, horizon, best_move, outcome, bound };
                 }
             },
             None => {
-                self.0.insert(
-                    hash_key,
-                    TranspositionTableEntry { horizon, best_move, outcome, bound }
-                );
+                self.entries[index] = Some(TranspositionTableEntry { hash_key, horizon, best_move, outcome, bound });
+                self.len += 1;
             }
         }
     }
 
+    fn get_entry(&self, hash_key: u64) -> Option<&TranspositionTableEntry> {
+        match &self.entries[(hash_key & self.mask) as usize] {
+            Some(e) if e.hash_key == hash_key => Some(e),
+            _ => None
+        }
+    }
+
     pub fn get(&self, hash_key: u64, horizon: i32) -> Option<(Move_, Outcome, Bound)> {
-        if let Some(e) = self.0.get(&hash_key) {
+        if let Some(e) = self.get_entry(hash_key) {
             if e.horizon >= horizon {
                 return Some((e.best_move, e.outcome, e.bound));
             }
@@ -59,9 +101,6 @@ impl TranspositionTable {
     }
 
     pub fn get_best_move(&self, hash_hey: u64) -> Option<Move_> {
-        match self.0.get(&hash_hey) {
-            Some(e) => Some(e.best_move),
-            None => None
-        }
+        self.get_entry(hash_hey).map(|e| e.best_move)
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,278 @@
+use std::fs;
+use std::sync::atomic::AtomicBool;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+use crate::error::HaddockError;
+use crate::evalparams::EvalParams;
+use crate::evaluation;
+use crate::generator::Generator;
+use crate::hash_counter::HashCounter;
+use crate::parser;
+use crate::position::Position;
+use crate::searcher::Searcher;
+
+const DEFAULT_OUTPUT: &str = "tuned.txt";
+const DEFAULT_ITERATIONS: u32 = 100;
+const DEFAULT_STEP: i32 = 1;
+
+pub struct TuneConfig {
+    pub input: String,
+    pub output: String,
+    pub params: Option<String>,
+    pub iterations: u32,
+    pub step: i32,
+    pub limit: usize //0 for all positions
+}
+
+//a quiet position with the result of its game, 1 is a white win, 0 a black win
+pub struct TuningPosition {
+    pub position: Position,
+    pub result: f64
+}
+
+//<file.epd> -output <file> -params <file> -iterations <n> -step <n> -limit <n>
+pub fn parse_args(args: &[String]) -> Result<TuneConfig, String> {
+    let mut config = TuneConfig {
+        input: String::new(),
+        output: DEFAULT_OUTPUT.to_string(),
+        params: None,
+        iterations: DEFAULT_ITERATIONS,
+        step: DEFAULT_STEP,
+        limit: 0
+    };
+
+    let mut i = 0;
+    while i < args.len() {
+        let option = args[i].as_str();
+        if !option.starts_with('-') {
+            if !config.input.is_empty() {
+                return Err(format!("unexpected argument {}", option));
+            }
+            config.input = option.to_string();
+            i += 1;
+            continue;
+        }
+
+        let value = args.get(i + 1).ok_or(format!("{} expects one value", option))?;
+        //values that do not fit the setting are invalid, not truncated
+        let invalid = || format!("invalid value {} for {}", value, option);
+        match option {
+            "-output" => config.output = value.to_string(),
+            "-params" => config.params = Some(value.to_string()),
+            "-iterations" => config.iterations = value.parse().map_err(|_| invalid())?,
+            "-step" => config.step = value.parse().map_err(|_| invalid())?,
+            "-limit" => config.limit = value.parse().map_err(|_| invalid())?,
+            _ => return Err(format!("unknown option {}", option))
+        }
+        i += 2;
+    }
+
+    if config.input.is_empty() {
+        return Err("no input file".to_string());
+    }
+    if config.step < 1 {
+        return Err("step must be at least 1".to_string());
+    }
+    Ok(config)
+}
+
+//epd lines with the game result as "1-0", "0-1", "1/2-1/2" or [1.0], [0.5], [0.0]
+pub fn parse_labelled_epd(content: &str) -> Result<Vec<(Position, f64)>, HaddockError> {
+    let mut result = Vec::new();
+
+    for (i, line) in content.lines().enumerate() {
+        let fen_parts = line.split_whitespace().take(4).collect::<Vec<&str>>();
+        if fen_parts.is_empty() || fen_parts[0].starts_with('#') {
+            continue;
+        }
+        if fen_parts.len() < 4 {
+            return Err(HaddockError::InvalidEpd(format!("line {}: incomplete position", i + 1)));
+        }
+
+        let position = parser::parse_fen(&fen_parts).map_err(|e| HaddockError::InvalidEpd(format!("line {}: {}", i + 1, e)))?;
+        let operations = line.split_whitespace().skip(4).collect::<Vec<&str>>().join(" ");
+        match get_result(&operations) {
+            Some(r) => result.push((position, r)),
+            None => return Err(HaddockError::InvalidEpd(format!("line {}: no game result", i + 1)))
+        }
+    }
+
+    Ok(result)
+}
+
+//game result from white's side in the operations of an epd line
+pub fn get_result(operations: &str) -> Option<f64> {
+    if operations.contains("1/2-1/2") || operations.contains("[0.5]") {
+        Some(0.5)
+    } else if operations.contains("1-0") || operations.contains("[1.0]") {
+        Some(1.0)
+    } else if operations.contains("0-1") || operations.contains("[0.0]") {
+        Some(0.0)
+    } else {
+        None
+    }
+}
+
+//plays the quiescence search variant of every position, positions in check are left out
+pub fn get_quiet_positions(positions: &[(Position, f64)]) -> Vec<TuningPosition> {
+    let (_sender, receiver) = mpsc::channel();
+    let start_position = parser::parse_startpos().expect("Error in startpos FEN");
+    let mut searcher = Searcher::new(receiver, start_position, Arc::new(AtomicBool::new(false)), HashCounter::new());
+
+    let mut result = Vec::new();
+    for &(position, game_result) in positions {
+        if Generator::new(&position).is_check(position.get_active_color()) {
+            continue;
+        }
+
+        searcher.set_position(position, HashCounter::new());
+        if let Some((_, variant)) = searcher.quiesce() {
+            let mut quiet = position;
+            for mv in variant {
+                quiet.apply_move(mv);
+            }
+            result.push(TuningPosition { position: quiet, result: game_result });
+        }
+    }
+    result
+}
+
+//expected score for white of an evaluation, k scales centipawns to winning chances
+pub fn sigmoid(value: i32, k: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-k * value as f64 / 400.0))
+}
+
+//mean squared error between the game results and the expected scores of the evaluation
+pub fn get_error(positions: &[TuningPosition], params: &EvalParams, k: f64) -> f64 {
+    if positions.is_empty() {
+        return 0.0;
+    }
+
+    let threads = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    let chunk_size = positions.len().div_ceil(threads);
+    let total: f64 = thread::scope(|s| {
+        let handles = positions.chunks(chunk_size)
+            .map(|chunk| s.spawn(move || chunk.iter()
+                .map(|p| {
+                    let value = evaluation::evaluate_with_params(&p.position, 0, params).score();
+                    (p.result - sigmoid(value, k)).powi(2)
+                })
+                .sum::<f64>()))
+            .collect::<Vec<_>>();
+        handles.into_iter().map(|h| h.join().expect("Error in tuning thread")).sum()
+    });
+    total / positions.len() as f64
+}
+
+//k with the lowest error for the given weights, narrowed down in steps of a tenth
+pub fn find_k(positions: &[TuningPosition], params: &EvalParams) -> f64 {
+    let mut best_k = 1.0;
+    let mut step = 1.0;
+    for _ in 0..4 {
+        let mut best_error = get_error(positions, params, best_k);
+        let start = best_k;
+        for i in -10..=10 {
+            let k = start + i as f64 * step / 10.0;
+            if k <= 0.0 {
+                continue;
+            }
+            let error = get_error(positions, params, k);
+            if error < best_error {
+                best_error = error;
+                best_k = k;
+            }
+        }
+        step /= 10.0;
+    }
+    best_k
+}
+
+//local search: every weight is moved by the step in both directions as long as the error goes down
+//a weight whose moves leave the error unchanged in an iteration is skipped in the later ones
+pub fn tune(
+    positions: &[TuningPosition],
+    start_params: &EvalParams,
+    k: f64,
+    step: i32,
+    iterations: u32,
+    on_iteration: &mut dyn FnMut(u32, f64, &EvalParams)
+) -> EvalParams {
+    let mut params = start_params.clone();
+    let mut values = params.to_values();
+    let mut active = vec![true; values.len()];
+    let mut best_error = get_error(positions, &params, k);
+
+    for iteration in 1..=iterations {
+        let mut improved = false;
+        for i in 0..values.len() {
+            if !active[i] {
+                continue;
+            }
+
+            let mut has_effect = false;
+            for &delta in [step, -step].iter() {
+                values[i] += delta;
+                params.set_values(&values);
+                let error = get_error(positions, &params, k);
+                if error < best_error {
+                    best_error = error;
+                    improved = true;
+                    has_effect = true;
+                    break;
+                }
+                if error != best_error {
+                    has_effect = true;
+                }
+                values[i] -= delta;
+            }
+            params.set_values(&values);
+            active[i] = has_effect;
+        }
+
+        on_iteration(iteration, best_error, &params);
+        if !improved {
+            break;
+        }
+    }
+    params
+}
+
+//the tuned weights are written after every iteration, so an interrupted run keeps its progress
+pub fn run(config: &TuneConfig) -> Result<EvalParams, String> {
+    let params = match &config.params {
+        Some(file_name) => EvalParams::load(file_name).map_err(|e| e.to_string())?,
+        None => EvalParams::default()
+    };
+
+    let content = fs::read_to_string(&config.input).map_err(|e| format!("cannot read {}: {}", config.input, e))?;
+    let mut labelled = parse_labelled_epd(&content).map_err(|e| e.to_string())?;
+    if config.limit > 0 {
+        labelled.truncate(config.limit);
+    }
+    println!("Read {} positions from {}", labelled.len(), config.input);
+
+    let positions = get_quiet_positions(&labelled);
+    if positions.is_empty() {
+        return Err("no positions to tune with".to_string());
+    }
+    println!("Tuning with {} quiet positions", positions.len());
+
+    let k = find_k(&positions, &params);
+    println!("K {:.3}, start error {:.6}", k, get_error(&positions, &params, k));
+
+    let mut save_error = None;
+    let tuned = tune(&positions, &params, k, config.step, config.iterations, &mut |iteration, error, p| {
+        println!("Iteration {}, error {:.6}", iteration, error);
+        if let Err(e) = p.save(&config.output) {
+            save_error = Some(e.to_string());
+        }
+    });
+    if let Some(e) = save_error {
+        return Err(e);
+    }
+
+    println!("Tuned parameters written to {}", config.output);
+    Ok(tuned)
+}
@@ -6,9 +6,9 @@ pub fn parse(line: &str) -> Option<InputCommand> {
     for (i, &word) in words.iter().enumerate() {
         match word {
             "uci" => return Some(InputCommand::Uci),
-            "debug" => return Some(InputCommand::Debug),
+            "debug" => return Some(InputCommand::Debug(words.get(i + 1) == Some(&"on"))),
             "isready" => return Some(InputCommand::IsReady),
-            "setoption" => return Some(InputCommand::SetOption),
+            "setoption" => return Some(InputCommand::SetOption(get_rest_string(&words, i))),
             "register" => return Some(InputCommand::Register),
             "ucinewgame" => return Some(InputCommand::UciNewGame),
             "position" => return Some(InputCommand::Position(get_rest_string(&words, i))),
@@ -16,6 +16,8 @@ pub fn parse(line: &str) -> Option<InputCommand> {
             "stop" => return Some(InputCommand::Stop),
             "ponderhit" => return Some(InputCommand::PonderHit),
             "quit" => return Some(InputCommand::Quit),
+            "bench" => return Some(InputCommand::Bench(get_rest_string(&words, i))),
+            "eval" => return Some(InputCommand::Eval),
             _ => continue
         }
     }
@@ -28,4 +30,18 @@ fn get_rest_string(words: &Vec<&str>, i: usize) -> String {
         return words[i + 1 ..].join(" ")
     }
     "".to_string()
-}
\ No newline at end of file
+}
+
+//"name <id> [value <x>]", both name and value may contain spaces
+pub fn parse_setoption(args: &str) -> Option<(String, String)> {
+    let words = args.split_whitespace().collect::<Vec<&str>>();
+    if words.len() < 2 || words[0] != "name" {
+        return None;
+    }
+
+    match words.iter().position(|&w| w == "value") {
+        Some(i) if i > 1 => Some((words[1..i].join(" "), words[i + 1..].join(" "))),
+        Some(_) => None,
+        None => Some((words[1..].join(" "), "".to_string()))
+    }
+}
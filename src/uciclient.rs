@@ -0,0 +1,297 @@
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+use crate::command::InputCommand;
+use crate::global;
+use crate::move_::Move_;
+use crate::searchtype::SearchType;
+
+//client side of the UCI protocol, drives an external engine process
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InfoScore {
+    Cp(i32),
+    Mate(i32)
+}
+
+#[derive(Clone, Default, Debug, PartialEq)]
+pub struct InfoLine {
+    pub depth: Option<u32>,
+    pub seldepth: Option<u32>,
+    pub multipv: Option<u32>,
+    pub score: Option<InfoScore>,
+    pub lowerbound: bool,
+    pub upperbound: bool,
+    pub nodes: Option<u64>,
+    pub nps: Option<u64>,
+    pub time: Option<u64>,
+    pub hashfull: Option<u32>,
+    pub tbhits: Option<u64>,
+    pub currmove: Option<Move_>,
+    pub currmovenumber: Option<u32>,
+    pub pv: Vec<Move_>,
+    pub string: Option<String>
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct UciOption {
+    pub name: String,
+    pub option_type: String,
+    pub default: Option<String>,
+    pub min: Option<i64>,
+    pub max: Option<i64>,
+    pub vars: Vec<String>
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum EngineMessage {
+    IdName(String),
+    IdAuthor(String),
+    UciOk,
+    ReadyOk,
+    Option(UciOption),
+    Info(InfoLine),
+    BestMove(Option<Move_>, Option<Move_>),
+    Other(String)
+}
+
+pub struct UciClient {
+    name: String,
+    options: Vec<UciOption>,
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>
+}
+
+impl UciClient {
+    //starts the engine process and does the uci handshake
+    pub fn start(command: &str) -> Result<UciClient, String> {
+        let mut child = match Command::new(command)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn() {
+            Ok(c) => c,
+            Err(e) => return Err(format!("cannot start engine {}: {}", command, e))
+        };
+
+        let stdin = child.stdin.take().expect("Error getting engine stdin");
+        let stdout = BufReader::new(child.stdout.take().expect("Error getting engine stdout"));
+
+        let mut client = UciClient {
+            name: command.to_string(),
+            options: Vec::new(),
+            child,
+            stdin,
+            stdout
+        };
+
+        client.send(&InputCommand::Uci)?;
+        loop {
+            match client.read_message()? {
+                EngineMessage::IdName(name) => client.name = name,
+                EngineMessage::Option(option) => client.options.push(option),
+                EngineMessage::UciOk => break,
+                _ => ()
+            }
+        }
+
+        client.is_ready()?;
+        Ok(client)
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_options(&self) -> &[UciOption] {
+        &self.options
+    }
+
+    pub fn send(&mut self, command: &InputCommand) -> Result<(), String> {
+        self.send_line(&command.to_uci())
+    }
+
+    pub fn send_line(&mut self, line: &str) -> Result<(), String> {
+        writeln!(self.stdin, "{}", line)
+            .and_then(|_| self.stdin.flush())
+            .map_err(|e| format!("cannot send to engine {}: {}", self.name, e))
+    }
+
+    pub fn read_message(&mut self) -> Result<EngineMessage, String> {
+        let mut line = String::new();
+        match self.stdout.read_line(&mut line) {
+            Ok(0) => Err(format!("engine {} closed its output", self.name)),
+            Ok(_) => Ok(parse_engine_line(&line)),
+            Err(e) => Err(format!("cannot read from engine {}: {}", self.name, e))
+        }
+    }
+
+    //blocks until the engine answers readyok
+    pub fn is_ready(&mut self) -> Result<(), String> {
+        self.send(&InputCommand::IsReady)?;
+        loop {
+            if let EngineMessage::ReadyOk = self.read_message()? {
+                return Ok(());
+            }
+        }
+    }
+
+    pub fn set_option(&mut self, name: &str, value: &str) -> Result<(), String> {
+        self.send_line(&format!("setoption name {} value {}", name, value))
+    }
+
+    pub fn new_game(&mut self) -> Result<(), String> {
+        self.send(&InputCommand::UciNewGame)?;
+        self.is_ready()
+    }
+
+    pub fn set_position(&mut self, fen: &str, moves: &[Move_]) -> Result<(), String> {
+        self.send(&InputCommand::Position(get_position_args(fen, moves)))
+    }
+
+    //starts a search and skips all info lines until bestmove
+    pub fn go(&mut self, search_type: SearchType) -> Result<Option<Move_>, String> {
+        self.send(&InputCommand::Go(search_type.to_go_args()))?;
+
+        loop {
+            if let EngineMessage::BestMove(best_move, _) = self.read_message()? {
+                return Ok(best_move);
+            }
+        }
+    }
+}
+
+impl Drop for UciClient {
+    fn drop(&mut self) {
+        let _ = self.send(&InputCommand::Quit);
+        let _ = self.child.wait();
+    }
+}
+
+pub fn get_position_args(fen: &str, moves: &[Move_]) -> String {
+    let mut args = if fen == global::FEN_STARTPOS {
+        "startpos".to_string()
+    } else {
+        format!("fen {}", fen)
+    };
+    if !moves.is_empty() {
+        args.push_str(" moves");
+        for mv in moves {
+            args.push(' ');
+            args.push_str(&mv.to_fen());
+        }
+    }
+    args
+}
+
+pub fn parse_engine_line(line: &str) -> EngineMessage {
+    let words = line.split_whitespace().collect::<Vec<&str>>();
+    if words.is_empty() {
+        return EngineMessage::Other(line.to_string());
+    }
+
+    match words[0] {
+        "id" if words.len() > 2 && words[1] == "name" => EngineMessage::IdName(words[2..].join(" ")),
+        "id" if words.len() > 2 && words[1] == "author" => EngineMessage::IdAuthor(words[2..].join(" ")),
+        "uciok" => EngineMessage::UciOk,
+        "readyok" => EngineMessage::ReadyOk,
+        "option" => match parse_option(&words[1..]) {
+            Some(option) => EngineMessage::Option(option),
+            None => EngineMessage::Other(line.to_string())
+        },
+        "info" => EngineMessage::Info(parse_info(&words[1..])),
+        "bestmove" => {
+            let best_move = words.get(1).and_then(|w| Move_::from_str(w).ok());
+            let ponder = match (words.get(2), words.get(3)) {
+                (Some(&"ponder"), Some(w)) => Move_::from_str(w).ok(),
+                _ => None
+            };
+            EngineMessage::BestMove(best_move, ponder)
+        }
+        _ => EngineMessage::Other(line.to_string())
+    }
+}
+
+pub fn parse_info(words: &[&str]) -> InfoLine {
+    let mut info = InfoLine::default();
+
+    let mut i = 0;
+    while i < words.len() {
+        let next = words.get(i + 1).copied().unwrap_or("");
+        match words[i] {
+            "depth" => { info.depth = next.parse().ok(); i += 1; }
+            "seldepth" => { info.seldepth = next.parse().ok(); i += 1; }
+            "multipv" => { info.multipv = next.parse().ok(); i += 1; }
+            "nodes" => { info.nodes = next.parse().ok(); i += 1; }
+            "nps" => { info.nps = next.parse().ok(); i += 1; }
+            "time" => { info.time = next.parse().ok(); i += 1; }
+            "hashfull" => { info.hashfull = next.parse().ok(); i += 1; }
+            "tbhits" => { info.tbhits = next.parse().ok(); i += 1; }
+            "currmove" => { info.currmove = Move_::from_str(next).ok(); i += 1; }
+            "currmovenumber" => { info.currmovenumber = next.parse().ok(); i += 1; }
+            "score" => {
+                let value = words.get(i + 2).and_then(|w| w.parse::<i32>().ok());
+                info.score = match (next, value) {
+                    ("cp", Some(v)) => Some(InfoScore::Cp(v)),
+                    ("mate", Some(v)) => Some(InfoScore::Mate(v)),
+                    _ => None
+                };
+                i += 2;
+            }
+            "lowerbound" => info.lowerbound = true,
+            "upperbound" => info.upperbound = true,
+            "pv" => {
+                //pv runs till the end of the line
+                info.pv = words[i + 1..].iter().filter_map(|w| Move_::from_str(w).ok()).collect();
+                break;
+            }
+            "string" => {
+                info.string = Some(words[i + 1..].join(" "));
+                break;
+            }
+            _ => ()
+        }
+        i += 1;
+    }
+
+    info
+}
+
+fn parse_option(words: &[&str]) -> Option<UciOption> {
+    //option names and values may contain spaces, so collect words until the next keyword
+    let keywords = ["name", "type", "default", "min", "max", "var"];
+    let mut option = UciOption {
+        name: "".to_string(),
+        option_type: "".to_string(),
+        default: None,
+        min: None,
+        max: None,
+        vars: Vec::new()
+    };
+
+    let mut i = 0;
+    while i < words.len() {
+        let keyword = words[i];
+        let mut j = i + 1;
+        while j < words.len() && !keywords.contains(&words[j]) {
+            j += 1;
+        }
+        let value = words[i + 1..j].join(" ");
+        match keyword {
+            "name" => option.name = value,
+            "type" => option.option_type = value,
+            "default" => option.default = Some(value),
+            "min" => option.min = value.parse().ok(),
+            "max" => option.max = value.parse().ok(),
+            "var" => option.vars.push(value),
+            _ => return None
+        }
+        i = j;
+    }
+
+    if option.name.is_empty() {
+        return None;
+    }
+    Some(option)
+}
@@ -0,0 +1,89 @@
+use std::io::{BufRead, Write};
+use std::sync::mpsc::{Sender, Receiver};
+use std::sync::mpsc;
+use std::thread;
+
+use crate::command;
+use crate::command::InputCommand;
+use crate::game::Game;
+use crate::logger::Logger;
+use crate::uci;
+
+//drives the uci protocol over any input and output, e.g. stdin/stdout or pipes in tests
+//input is read on the calling thread, so nothing is left running after quit
+pub struct UciEngine {
+    logger: Logger
+}
+
+impl UciEngine {
+    pub fn new(output: Box<dyn Write + Send>) -> UciEngine {
+        UciEngine { logger: Logger::new(output) }
+    }
+
+    //blocks till quit is received or the input is closed
+    pub fn run<R: BufRead>(&mut self, mut input: R) {
+        let (game_handle, game_channel) = self.setup_game();
+
+        loop {
+            let mut line = String::new();
+            match input.read_line(&mut line) {
+                Ok(0) => break,
+                Ok(_) => (),
+                Err(e) => {
+                    self.logger.debug(&format!("Error while receiving input : {}", e));
+                    break;
+                }
+            }
+
+            self.logger.log_input(&line);
+            if !self.handle_line(&line, &game_channel) {
+                break;
+            }
+        }
+
+        //wait till game thread finished
+        game_channel.send(InputCommand::Quit).expect("Error cleaning up");
+        game_handle.join().expect("Error while synchronizing with game thread");
+    }
+
+    fn handle_line(&self, line: &str, game_channel: &Sender<InputCommand>) -> bool {
+        match uci::parse(line) {
+            Some(InputCommand::Quit) => false,
+            //isready is answered by the game, after all commands before it are handled
+            Some(c @ InputCommand::IsReady) |
+            Some(c @ InputCommand::SetOption(_)) |
+            Some(c @ InputCommand::UciNewGame) |
+            Some(c @ InputCommand::Stop) |
+            Some(c @ InputCommand::Position(_)) |
+            Some(c @ InputCommand::Go(_)) |
+            Some(c @ InputCommand::Bench(_)) |
+            Some(c @ InputCommand::Eval) => {
+                game_channel.send(c).expect("Error sending command");
+                true
+            }
+            Some(c) => {
+                let result = command::send_command(c, &self.logger);
+                for message_line in result.message.lines() {
+                    self.logger.send(message_line);
+                }
+                result.stay
+            }
+            None => {
+                self.logger.send("info string unknown command");
+                true
+            }
+        }
+    }
+
+    fn setup_game(&self) -> (thread::JoinHandle<()>, Sender<InputCommand>) {
+        let (sender, receiver): (Sender<InputCommand>, Receiver<InputCommand>) = mpsc::channel();
+
+        let logger = self.logger.clone();
+        let game_handle = thread::spawn(move || {
+            let mut game = Game::new(receiver, logger);
+            game.start();
+        });
+
+        (game_handle, sender)
+    }
+}
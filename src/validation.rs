@@ -0,0 +1,105 @@
+use crate::error::HaddockError;
+use crate::generator::Generator;
+use crate::global;
+use crate::piecetype::PieceType;
+use crate::position::Position;
+use crate::square;
+use crate::square::Square;
+
+//checks that a position could occur in a game, e.g. before searching a position from a fen
+pub fn validate_position(position: &Position) -> Result<(), HaddockError> {
+    for color in [global::COLOR_WHITE, global::COLOR_BLACK].iter() {
+        validate_piece_counts(position, *color)?;
+    }
+    validate_pawns(position)?;
+    validate_castling_rights(position)?;
+    validate_enpassant_square(position)?;
+
+    //the side that just moved cannot have left its king in check
+    let other_color = 1 - position.get_active_color();
+    if Generator::new(position).is_check(other_color) {
+        return Err(HaddockError::OpponentInCheck);
+    }
+
+    Ok(())
+}
+
+fn validate_piece_counts(position: &Position, color: u8) -> Result<(), HaddockError> {
+    match position.get_piece_count(PieceType::new_king(color)) {
+        0 => return Err(HaddockError::KingMissing(color)),
+        1 => (),
+        _ => return Err(HaddockError::TooManyKings(color))
+    }
+
+    let pawns = position.get_piece_count(PieceType::new_pawn(color));
+    if pawns > 8 {
+        return Err(HaddockError::TooManyPawns(color));
+    }
+
+    //every piece above the initial count needs a promoted pawn
+    let promoted =
+        (position.get_piece_count(PieceType::new_queen(color)) - 1).max(0) +
+        (position.get_piece_count(PieceType::new_rook(color)) - 2).max(0) +
+        (position.get_piece_count(PieceType::new_bishop(color)) - 2).max(0) +
+        (position.get_piece_count(PieceType::new_knight(color)) - 2).max(0);
+    if promoted > 8 - pawns || position.get_piece_board(color).get_count() > 16 {
+        return Err(HaddockError::TooManyPieces(color));
+    }
+
+    Ok(())
+}
+
+fn validate_pawns(position: &Position) -> Result<(), HaddockError> {
+    for color in [global::COLOR_WHITE, global::COLOR_BLACK].iter() {
+        for square in position.get_bit_board(PieceType::new_pawn(*color)).get_squares() {
+            let (_, y) = square.to_xy();
+            if y == 0 || y == 7 {
+                return Err(HaddockError::PawnOnBackRank(square.to_fen()));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn validate_castling_rights(position: &Position) -> Result<(), HaddockError> {
+    //castling status index, castling char, king square, rook square, color
+    let rights = [
+        (0, 'K', square::E1, square::H1, global::COLOR_WHITE),
+        (1, 'Q', square::E1, square::A1, global::COLOR_WHITE),
+        (2, 'k', square::E8, square::H8, global::COLOR_BLACK),
+        (3, 'q', square::E8, square::A8, global::COLOR_BLACK)
+    ];
+
+    for &(index, c, king_square, rook_square, color) in rights.iter() {
+        if position.get_castling_status(index) &&
+            (position.get_piece(king_square) != Some(PieceType::new_king(color)) ||
+             position.get_piece(rook_square) != Some(PieceType::new_rook(color))) {
+            return Err(HaddockError::InvalidCastlingRights(c));
+        }
+    }
+    Ok(())
+}
+
+//the ep square must be behind a pawn that just made a double step
+fn validate_enpassant_square(position: &Position) -> Result<(), HaddockError> {
+    let ep_square = match position.get_enpassant_square() {
+        Some(s) => s,
+        None => return Ok(())
+    };
+
+    let (x, y) = ep_square.to_xy();
+    let (ep_y, from_y, pawn_y) = if position.get_active_color() == global::COLOR_WHITE {
+        (5, 6, 4)
+    } else {
+        (2, 1, 3)
+    };
+    let pawn = PieceType::new_pawn(1 - position.get_active_color());
+
+    if y != ep_y ||
+        position.get_piece(ep_square).is_some() ||
+        position.get_piece(Square::from_xy(x, from_y)).is_some() ||
+        position.get_piece(Square::from_xy(x, pawn_y)) != Some(pawn) {
+        return Err(HaddockError::InvalidEnPassantSquare(ep_square.to_fen()));
+    }
+    Ok(())
+}
@@ -0,0 +1,13 @@
+use std::process::Command;
+
+//failed command line tools end with a non zero exit status and their error on stderr
+#[test]
+fn test_tool_error_exit_status() {
+    //act
+    let output = Command::new(env!("CARGO_BIN_EXE_haddock")).args(["tune", "-step", "x"]).output().unwrap();
+
+    //assert
+    assert_eq!(Some(1), output.status.code());
+    assert!(output.stdout.is_empty(), "Error written to stdout");
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("Error in tune: "), "No error on stderr");
+}
@@ -0,0 +1,373 @@
+extern crate haddock;
+
+use std::io::{self, BufReader, Read, Write};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+use haddock::uciengine::UciEngine;
+
+//input fed line by line from the test, closing the sender ends the input
+struct ChannelReader {
+    receiver: Receiver<String>,
+    buffer: Vec<u8>
+}
+
+impl Read for ChannelReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.buffer.is_empty() {
+            match self.receiver.recv() {
+                Ok(line) => self.buffer = format!("{}\n", line).into_bytes(),
+                Err(_) => return Ok(0)
+            }
+        }
+        let n = std::cmp::min(buf.len(), self.buffer.len());
+        buf[..n].copy_from_slice(&self.buffer[..n]);
+        self.buffer.drain(..n);
+        Ok(n)
+    }
+}
+
+//output sent back to the test line by line
+struct ChannelWriter {
+    sender: Sender<String>,
+    buffer: Vec<u8>
+}
+
+impl Write for ChannelWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        while let Some(i) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line = String::from_utf8_lossy(&self.buffer[..i]).to_string();
+            self.buffer.drain(..=i);
+            let _ = self.sender.send(line);
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn start_engine() -> (thread::JoinHandle<()>, Sender<String>, Receiver<String>) {
+    let (input_sender, input_receiver) = mpsc::channel();
+    let (output_sender, output_receiver) = mpsc::channel();
+
+    let handle = thread::spawn(move || {
+        let writer = ChannelWriter { sender: output_sender, buffer: Vec::new() };
+        let reader = ChannelReader { receiver: input_receiver, buffer: Vec::new() };
+        let mut engine = UciEngine::new(Box::new(writer));
+        engine.run(BufReader::new(reader));
+    });
+
+    (handle, input_sender, output_receiver)
+}
+
+fn read_until(output: &Receiver<String>, prefix: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    loop {
+        let line = output.recv_timeout(Duration::from_secs(30)).expect("No output from engine");
+        let done = line.starts_with(prefix);
+        lines.push(line);
+        if done {
+            return lines;
+        }
+    }
+}
+
+#[test]
+fn test_engine_search_and_quit() {
+    //arrange
+    let (handle, input, output) = start_engine();
+
+    //act
+    input.send("uci".to_string()).unwrap();
+    let uci_lines = read_until(&output, "uciok");
+    input.send("position startpos moves e2e4".to_string()).unwrap();
+    input.send("go depth 3".to_string()).unwrap();
+    let search_lines = read_until(&output, "bestmove");
+    input.send("quit".to_string()).unwrap();
+
+    //assert
+    assert!(uci_lines[0].starts_with("id name haddock"), "Unexpected id {}", uci_lines[0]);
+    assert!(search_lines.iter().any(|l| l.starts_with("info depth 3 ")), "No info for depth 3");
+    assert!(search_lines.iter().all(|l| l.starts_with("info ") || l.starts_with("bestmove ")), "Non uci output during search");
+    handle.join().expect("Engine did not shut down");
+}
+
+#[test]
+fn test_engine_stops_at_end_of_input() {
+    //arrange
+    let (handle, input, output) = start_engine();
+
+    //act
+    input.send("isready".to_string()).unwrap();
+    let lines = read_until(&output, "readyok");
+    drop(input);
+
+    //assert
+    assert_eq!(vec!["readyok".to_string()], lines);
+    handle.join().expect("Engine did not shut down");
+}
+
+#[test]
+fn test_engine_debug_info_string() {
+    //arrange
+    let (handle, input, output) = start_engine();
+
+    //act
+    input.send("debug on".to_string()).unwrap();
+    input.send("position startpos".to_string()).unwrap();
+    input.send("go depth 3".to_string()).unwrap();
+    let lines = read_until(&output, "bestmove");
+    input.send("quit".to_string()).unwrap();
+
+    //assert
+    assert!(lines.iter().any(|l| l.starts_with("info string ")), "No debug output");
+    handle.join().expect("Engine did not shut down");
+}
+
+#[test]
+fn test_engine_isready_after_position() {
+    //arrange
+    let (handle, input, output) = start_engine();
+
+    //act
+    input.send("position startpos moves e2e4 e7e5 g1f3".to_string()).unwrap();
+    input.send("isready".to_string()).unwrap();
+    let ready_lines = read_until(&output, "readyok");
+    input.send("go depth 2".to_string()).unwrap();
+    let search_lines = read_until(&output, "bestmove");
+    input.send("quit".to_string()).unwrap();
+
+    //assert
+    assert_eq!(vec!["readyok".to_string()], ready_lines);
+    assert!(search_lines.last().unwrap().starts_with("bestmove "), "No best move");
+    handle.join().expect("Engine did not shut down");
+}
+
+#[test]
+fn test_engine_isready_during_search() {
+    //arrange
+    let (handle, input, output) = start_engine();
+
+    //act
+    input.send("position startpos".to_string()).unwrap();
+    input.send("go infinite".to_string()).unwrap();
+    thread::sleep(Duration::from_millis(100));
+    input.send("isready".to_string()).unwrap();
+    let ready_lines = read_until(&output, "readyok");
+    input.send("stop".to_string()).unwrap();
+    let search_lines = read_until(&output, "bestmove");
+    input.send("quit".to_string()).unwrap();
+
+    //assert
+    assert!(ready_lines.iter().all(|l| !l.starts_with("bestmove")), "Search finished before readyok");
+    assert!(search_lines.last().unwrap().starts_with("bestmove "), "No best move");
+    handle.join().expect("Engine did not shut down");
+}
+
+#[test]
+fn test_engine_ucinewgame() {
+    //arrange
+    let (handle, input, output) = start_engine();
+    let get_nodes = |lines: Vec<String>| lines.iter()
+        .find(|l| l.starts_with("info depth 3 "))
+        .map(|l| l.split_whitespace().skip_while(|&w| w != "nodes").nth(1).unwrap().to_string())
+        .expect("No info for depth 3");
+
+    //act
+    input.send("position startpos".to_string()).unwrap();
+    input.send("go depth 3".to_string()).unwrap();
+    let nodes_first = get_nodes(read_until(&output, "bestmove"));
+    input.send("go depth 3".to_string()).unwrap();
+    let nodes_same_game = get_nodes(read_until(&output, "bestmove"));
+    input.send("ucinewgame".to_string()).unwrap();
+    input.send("isready".to_string()).unwrap();
+    let ready_lines = read_until(&output, "readyok");
+    input.send("position startpos".to_string()).unwrap();
+    input.send("go depth 3".to_string()).unwrap();
+    let nodes_new_game = get_nodes(read_until(&output, "bestmove"));
+    input.send("quit".to_string()).unwrap();
+
+    //assert
+    assert_eq!(vec!["readyok".to_string()], ready_lines);
+    assert_ne!(nodes_first, nodes_same_game, "Tables not kept between searches");
+    assert_eq!(nodes_first, nodes_new_game, "Tables not cleared on new game");
+    handle.join().expect("Engine did not shut down");
+}
+
+#[test]
+fn test_engine_isready_after_ucinewgame() {
+    //arrange
+    let (handle, input, output) = start_engine();
+
+    //act
+    input.send("debug on".to_string()).unwrap();
+    input.send("ucinewgame".to_string()).unwrap();
+    input.send("isready".to_string()).unwrap();
+    let lines = read_until(&output, "readyok");
+    input.send("quit".to_string()).unwrap();
+
+    //assert
+    //the tables are cleared by the searcher thread before readyok is sent
+    assert_eq!(vec!["info string tables cleared for new game".to_string(), "readyok".to_string()], lines);
+    handle.join().expect("Engine did not shut down");
+}
+
+#[test]
+fn test_engine_search_again_after_stop() {
+    //arrange
+    let (handle, input, output) = start_engine();
+
+    //act
+    input.send("position startpos".to_string()).unwrap();
+    input.send("go infinite".to_string()).unwrap();
+    thread::sleep(Duration::from_millis(100));
+    input.send("stop".to_string()).unwrap();
+    let stopped_lines = read_until(&output, "bestmove");
+    input.send("position startpos moves e2e4".to_string()).unwrap();
+    input.send("go depth 2".to_string()).unwrap();
+    let search_lines = read_until(&output, "bestmove");
+    input.send("quit".to_string()).unwrap();
+
+    //assert
+    assert!(stopped_lines.last().unwrap().starts_with("bestmove "), "No best move after stop");
+    assert!(search_lines.iter().any(|l| l.starts_with("info depth 2 ")), "No search after stop");
+    handle.join().expect("Engine did not shut down");
+}
+
+#[test]
+fn test_engine_keeps_position_on_error() {
+    //arrange
+    let (handle, input, output) = start_engine();
+
+    //act
+    input.send("position fen 4k3/8/8/8/8/8/8/R3K3 w - - 0 1".to_string()).unwrap();
+    input.send("position startpos moves e2e4 e7e6 e4e6".to_string()).unwrap();
+    let error_lines = read_until(&output, "info string");
+    input.send("go depth 2".to_string()).unwrap();
+    let search_lines = read_until(&output, "bestmove");
+    input.send("quit".to_string()).unwrap();
+
+    //assert
+    assert_eq!(vec!["info string illegal move e4e6".to_string()], error_lines);
+    assert!(search_lines.last().unwrap().starts_with("bestmove a1") || search_lines.last().unwrap().starts_with("bestmove e1"), "Previous position not kept");
+    handle.join().expect("Engine did not shut down");
+}
+
+#[test]
+fn test_engine_contempt() {
+    //arrange
+    //every move reaches the fifty move rule, so the score is the draw score
+    let (handle, input, output) = start_engine();
+    let get_score = |lines: Vec<String>| lines.iter()
+        .find(|l| l.starts_with("info depth 1 "))
+        .map(|l| l.split_whitespace().skip_while(|&w| w != "score").take(3).collect::<Vec<&str>>().join(" "))
+        .expect("No info for depth 1");
+
+    //act
+    input.send("setoption name Contempt value 50".to_string()).unwrap();
+    input.send("position fen 4k3/8/8/8/8/8/8/R3K3 w - - 99 80".to_string()).unwrap();
+    input.send("go depth 2".to_string()).unwrap();
+    let score_white = get_score(read_until(&output, "bestmove"));
+    input.send("position fen r3k3/8/8/8/8/8/8/4K3 b - - 99 80".to_string()).unwrap();
+    input.send("go depth 2".to_string()).unwrap();
+    let score_black = get_score(read_until(&output, "bestmove"));
+    input.send("setoption name UCI_AnalyseMode value true".to_string()).unwrap();
+    input.send("setoption name Analysis Contempt value false".to_string()).unwrap();
+    input.send("position fen 4k3/8/8/8/8/8/8/R3K3 w - - 99 80".to_string()).unwrap();
+    input.send("go depth 2".to_string()).unwrap();
+    let score_analysis = get_score(read_until(&output, "bestmove"));
+    input.send("quit".to_string()).unwrap();
+
+    //assert
+    assert_eq!("score cp -50", score_white);
+    assert_eq!("score cp -50", score_black);
+    assert_eq!("score cp 0", score_analysis);
+    handle.join().expect("Engine did not shut down");
+}
+
+#[test]
+fn test_engine_eval() {
+    //arrange
+    let (handle, input, output) = start_engine();
+
+    //act
+    input.send("position startpos moves e2e4".to_string()).unwrap();
+    input.send("eval".to_string()).unwrap();
+    let lines = read_until(&output, "Evaluation:");
+    input.send("quit".to_string()).unwrap();
+
+    //assert
+    assert!(lines.iter().any(|l| l == "4 ....P..."), "Position not printed");
+    assert!(lines.iter().any(|l| l.trim_start().starts_with("Total |")), "Total missing");
+    assert!(lines.iter().any(|l| l.starts_with("Phase: ")), "Phase missing");
+    handle.join().expect("Engine did not shut down");
+}
+
+#[test]
+fn test_engine_eval_file() {
+    //arrange
+    let path = std::env::temp_dir().join(format!("haddock_eval_file_{}.txt", std::process::id()));
+    std::fs::write(&path, "piece_value 200 200 0 0 900 900 500 500 300 300 300 300\n").unwrap();
+    let (handle, input, output) = start_engine();
+    let get_material = |lines: Vec<String>| lines.iter()
+        .find(|l| l.trim_start().starts_with("Material |"))
+        .map(|l| l.trim().to_string())
+        .expect("No material line");
+
+    //act
+    input.send("position fen 4k3/pppppppp/8/8/8/8/PPPPPPPP/4K3 w - - 0 1".to_string()).unwrap();
+    input.send("eval".to_string()).unwrap();
+    let material_default = get_material(read_until(&output, "Evaluation:"));
+    input.send(format!("setoption name EvalFile value {}", path.display())).unwrap();
+    input.send("eval".to_string()).unwrap();
+    let material_file = get_material(read_until(&output, "Evaluation:"));
+    input.send("setoption name EvalFile value /nonexistent/haddock.txt".to_string()).unwrap();
+    input.send("isready".to_string()).unwrap();
+    let error_lines = read_until(&output, "readyok");
+    input.send("quit".to_string()).unwrap();
+
+    //assert
+    assert!(material_default.starts_with("Material |   800   800 |"), "Unexpected {}", material_default);
+    assert!(material_file.starts_with("Material |  1600  1600 |"), "Unexpected {}", material_file);
+    assert!(error_lines[0].starts_with("info string invalid evaluation file"), "No error for missing file");
+    handle.join().expect("Engine did not shut down");
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_engine_nnue() {
+    //arrange
+    //no feature weights, the side to move is always 100 centipawns ahead
+    let path = std::env::temp_dir().join(format!("haddock_nnue_{}.bin", std::process::id()));
+    let mut network = haddock::nnue::Network::new();
+    network.output_bias = 100 * haddock::nnue::QA * haddock::nnue::QB / haddock::nnue::SCALE;
+    network.save(path.to_str().unwrap()).unwrap();
+    let (handle, input, output) = start_engine();
+
+    //act
+    input.send("setoption name Use NNUE value true".to_string()).unwrap();
+    input.send("isready".to_string()).unwrap();
+    let missing_lines = read_until(&output, "readyok");
+    input.send(format!("setoption name NNUE File value {}", path.display())).unwrap();
+    input.send("position startpos moves e2e4".to_string()).unwrap();
+    input.send("eval".to_string()).unwrap();
+    let eval_lines = read_until(&output, "NNUE evaluation:");
+    input.send("go depth 3".to_string()).unwrap();
+    let search_lines = read_until(&output, "bestmove");
+    input.send("setoption name NNUE File value /nonexistent/haddock.bin".to_string()).unwrap();
+    input.send("isready".to_string()).unwrap();
+    let error_lines = read_until(&output, "readyok");
+    input.send("quit".to_string()).unwrap();
+
+    //assert
+    assert!(missing_lines[0].starts_with("info string no network loaded"), "No message without network");
+    assert_eq!("NNUE evaluation: -100 (white side)", eval_lines.last().unwrap());
+    assert!(search_lines.iter().any(|l| l.starts_with("info depth 3 ")), "No info for depth 3");
+    assert!(error_lines[0].starts_with("info string invalid network file"), "No error for missing file");
+    handle.join().expect("Engine did not shut down");
+    std::fs::remove_file(&path).unwrap();
+}
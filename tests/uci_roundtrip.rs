@@ -0,0 +1,36 @@
+extern crate haddock;
+
+use haddock::command::InputCommand;
+use haddock::searchtype::SearchType;
+use haddock::uciclient::{EngineMessage, UciClient};
+
+//drives the haddock binary through its own uci implementation
+#[test]
+fn test_uci_roundtrip() {
+    //arrange
+    let mut client = UciClient::start(env!("CARGO_BIN_EXE_haddock")).unwrap();
+    client.new_game().unwrap();
+
+    //act
+    client.set_position(haddock::global::FEN_STARTPOS, &[]).unwrap();
+    client.send(&InputCommand::Go(SearchType::Depth(3).to_go_args())).unwrap();
+    let mut infos = Vec::new();
+    let best_move = loop {
+        match client.read_message().unwrap() {
+            EngineMessage::Info(info) => infos.push(info),
+            EngineMessage::BestMove(best_move, _) => break best_move,
+            _ => ()
+        }
+    };
+
+    //assert
+    assert!(client.get_name().starts_with("haddock"), "Unexpected engine name {}", client.get_name());
+    assert!(best_move.is_some(), "No best move");
+    let info = infos.iter().rev().find(|i| i.score.is_some()).expect("No info with score");
+    assert_eq!(Some(3), info.depth);
+    assert_eq!(best_move, info.pv.first().cloned(), "Best move not first pv move");
+    assert!(client.get_options().iter().any(|o| o.name == "Debug Log File"), "Debug Log File option missing");
+
+    client.set_position(haddock::global::FEN_STARTPOS, &[best_move.unwrap()]).unwrap();
+    assert!(client.go(SearchType::Depth(2)).unwrap().is_some(), "No best move after the first move");
+}